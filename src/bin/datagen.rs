@@ -0,0 +1,55 @@
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::Arc;
+use chess::engines::nnue::Network;
+use chess::simulator::datagen::Datagen;
+
+const USAGE: &str = "usage: datagen [--positions N] [--threads N] [--nodes N] [--search N] [--eval N] \
+[--network FILE] [--random-plies N] [--startpos] [--text FILE] <out.bin>";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut datagen = Datagen::new();
+    let mut text = None;
+    let mut files = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value\n{USAGE}"));
+        match arg.as_str() {
+            "--positions"    => datagen.positions    = parse(&value()?)?,
+            "--threads"      => datagen.threads      = parse(&value()?)?,
+            "--nodes"        => datagen.nodes        = parse(&value()?)?,
+            "--search"       => datagen.search_algo  = parse(&value()?)?,
+            "--eval"         => datagen.eval_algo    = parse(&value()?)?,
+            "--random-plies" => datagen.random_plies = parse(&value()?)?,
+            "--startpos"     => datagen.startpos     = true,
+            "--text"         => text = Some(value()?),
+            "--network"      => {
+                let path = value()?;
+                let network = Network::open(&path).map_err(|e| format!("{path}: {e}"))?;
+                datagen.network = Some(Arc::new(network));
+            }
+            _ => files.push(arg),
+        }
+    }
+    let [out] = files.as_slice() else {
+        return Err(USAGE.to_string());
+    };
+
+    let added = datagen.run(Path::new(out), text.as_deref().map(Path::new)).map_err(|e| format!("{out}: {e}"))?;
+    println!("wrote {added} positions to {out}");
+    Ok(())
+}
+
+fn parse<T: std::str::FromStr>(text: &str) -> Result<T, String> {
+    text.parse().map_err(|_| format!("bad number '{text}'\n{USAGE}"))
+}
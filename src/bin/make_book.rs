@@ -0,0 +1,47 @@
+use std::process::ExitCode;
+use chess::engines::book::BookBuilder;
+
+const USAGE: &str = "usage: make_book [--max-ply N] [--min-games N] [--min-score X] <out.bin> <games.pgn>...";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut builder = BookBuilder::new();
+    let mut files = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value\n{USAGE}"));
+        match arg.as_str() {
+            "--max-ply"   => builder.max_ply   = parse(&value()?)?,
+            "--min-games" => builder.min_games = parse(&value()?)?,
+            "--min-score" => builder.min_score = parse(&value()?)?,
+            _ => files.push(arg),
+        }
+    }
+    let Some((out, pgns)) = files.split_first().filter(|(_, pgns)| !pgns.is_empty()) else {
+        return Err(USAGE.to_string());
+    };
+
+    for path in pgns {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        let games = builder.add_pgn(&text).map_err(|e| format!("{path}: {e}"))?;
+        println!("{path}: {games} games");
+    }
+
+    let book = builder.build();
+    book.save(out).map_err(|e| format!("{out}: {e}"))?;
+    println!("wrote {} entries to {out}", book.len());
+    Ok(())
+}
+
+fn parse<T: std::str::FromStr>(text: &str) -> Result<T, String> {
+    text.parse().map_err(|_| format!("bad number '{text}'\n{USAGE}"))
+}
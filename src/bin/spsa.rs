@@ -0,0 +1,49 @@
+use std::path::Path;
+use std::process::ExitCode;
+use chess::engines::search_params::PARAM_SPECS;
+use chess::simulator::spsa::Spsa;
+
+const USAGE: &str = "usage: spsa [--iterations N] [--pairs N] [--threads N] [--time MS] [--nodes N] [--eval N] \
+[--learning-rate X] <log.csv>";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut spsa = Spsa::new();
+    let mut files = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value\n{USAGE}"));
+        match arg.as_str() {
+            "--iterations"    => spsa.iterations    = parse(&value()?)?,
+            "--pairs"         => spsa.pairs         = parse(&value()?)?,
+            "--threads"       => spsa.threads       = parse(&value()?)?,
+            "--time"          => spsa.time_ms       = parse(&value()?)?,
+            "--nodes"         => spsa.nodes         = Some(parse(&value()?)?),
+            "--eval"          => spsa.eval_algo     = parse(&value()?)?,
+            "--learning-rate" => spsa.learning_rate = parse(&value()?)?,
+            _ => files.push(arg),
+        }
+    }
+    let [log] = files.as_slice() else {
+        return Err(USAGE.to_string());
+    };
+
+    let params = spsa.run(Path::new(log)).map_err(|e| format!("{log}: {e}"))?;
+    for (spec, value) in PARAM_SPECS.iter().zip(params.values()) {
+        println!("{} = {value}", spec.name);
+    }
+    Ok(())
+}
+
+fn parse<T: std::str::FromStr>(text: &str) -> Result<T, String> {
+    text.parse().map_err(|_| format!("bad number '{text}'\n{USAGE}"))
+}
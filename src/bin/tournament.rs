@@ -0,0 +1,57 @@
+use std::process::ExitCode;
+use chess::engines::engine_manager::NUMBER_OF_EVAL_ALGORITHMS;
+use chess::simulator::engine_battle_simulator::Openings;
+use chess::simulator::tournament::{EngineSpec, Format, Tournament};
+
+const USAGE: &str = "usage: tournament [--gauntlet] [--games N] [--threads N] [--nodes N] [--random-openings] \
+[--all-searches [--eval N] [--time MS]] [search:eval:time_ms ...]";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut tournament = Tournament::new(Vec::new());
+    let (mut all_searches, mut eval, mut time_ms) = (false, NUMBER_OF_EVAL_ALGORITHMS, 10);
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value\n{USAGE}"));
+        match arg.as_str() {
+            "--gauntlet"        => tournament.format   = Format::Gauntlet,
+            "--games"           => tournament.games    = parse(&value()?)?,
+            "--threads"         => tournament.threads  = parse(&value()?)?,
+            "--nodes"           => tournament.nodes    = Some(parse(&value()?)?),
+            "--random-openings" => tournament.openings = Openings::Random,
+            "--all-searches"    => all_searches        = true,
+            "--eval"            => eval                = parse(&value()?)?,
+            "--time"            => time_ms             = parse(&value()?)?,
+            _ => tournament.engines.push(arg.parse()?),
+        }
+    }
+    if all_searches {
+        if !(1..=NUMBER_OF_EVAL_ALGORITHMS).contains(&eval) {
+            return Err(format!("no such eval {eval}\n{USAGE}"));
+        }
+        tournament.engines.extend(EngineSpec::all_searches(eval, time_ms));
+    }
+    if tournament.nodes == Some(0) {
+        return Err(format!("--nodes must be at least 1\n{USAGE}"));
+    }
+    if tournament.engines.len() < 2 {
+        return Err(USAGE.to_string());
+    }
+
+    let standings = tournament.run();
+    println!("{standings}");
+    Ok(())
+}
+
+fn parse<T: std::str::FromStr>(text: &str) -> Result<T, String> {
+    text.parse().map_err(|_| format!("bad number '{text}'\n{USAGE}"))
+}
@@ -0,0 +1,59 @@
+use std::path::Path;
+use std::process::ExitCode;
+use chess::tuner::{emit_rust, Tuner};
+
+const USAGE: &str = "usage: tune [--epochs N] [--learning-rate X] [--k X] [--out FILE] <positions>...";
+const DEFAULT_OUT: &str = "src/eval_params.rs";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut tuner = Tuner::new();
+    let mut k = None;
+    let mut out = DEFAULT_OUT.to_string();
+    let mut files = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value\n{USAGE}"));
+        match arg.as_str() {
+            "--epochs"        => tuner.epochs        = parse(&value()?)?,
+            "--learning-rate" => tuner.learning_rate = parse(&value()?)?,
+            "--k"             => k = Some(parse(&value()?)?),
+            "--out"           => out = value()?,
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    for path in &files {
+        let added = tuner.load(Path::new(path)).map_err(|e| format!("{path}: {e}"))?;
+        println!("{path}: {added} positions");
+    }
+    let k = k.unwrap_or_else(|| tuner.fit_k());
+    println!("K = {k:.4}, error {:.6}", tuner.error(k));
+
+    let epochs = tuner.epochs;
+    tuner.tune(k, |epoch, error| {
+        if epoch % 50 == 0 || epoch == epochs {
+            println!("epoch {epoch}: error {error:.6}");
+        }
+    });
+
+    std::fs::write(&out, emit_rust(&tuner.params())).map_err(|e| format!("{out}: {e}"))?;
+    println!("wrote {out}");
+    Ok(())
+}
+
+fn parse<T: std::str::FromStr>(text: &str) -> Result<T, String> {
+    text.parse().map_err(|_| format!("bad number '{text}'\n{USAGE}"))
+}
@@ -0,0 +1,5 @@
+use chess::uci::Uci;
+
+fn main() {
+    Uci::new().run();
+}
@@ -0,0 +1,236 @@
+use std::sync::OnceLock;
+use crate::attacks::movegen::all_moves;
+use crate::color::Color;
+use crate::piece::Piece;
+use crate::position::Position;
+
+/// Side to move (strong or weak) x strong king x weak king x piece square.
+const TABLE_SIZE: usize = 2 * 64 * 64 * 64;
+/// Score of a won bitbase ending before the terms that guide the search towards the win;
+/// stays below tablebase scores and the mate scores of every search.
+pub const KNOWN_WIN: i16 = 5_000;
+
+// successor codes that are not an index into the table being built
+const SUCC_WIN:  u32 = u32::MAX;
+const SUCC_DRAW: u32 = u32::MAX - 1;
+
+
+/// The three-piece endings covered: both kings plus one white or black pawn, rook or queen.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Ending {
+    Kpk,
+    Krk,
+    Kqk,
+}
+
+impl Ending {
+    fn piece(self) -> Piece {
+        match self {
+            Ending::Kpk => Piece::Pawn,
+            Ending::Krk => Piece::Rook,
+            Ending::Kqk => Piece::Queen,
+        }
+    }
+
+    fn table(self) -> &'static Bitbase {
+        static KPK: OnceLock<Bitbase> = OnceLock::new();
+        static KRK: OnceLock<Bitbase> = OnceLock::new();
+        static KQK: OnceLock<Bitbase> = OnceLock::new();
+        match self {
+            Ending::Kpk => KPK.get_or_init(|| Bitbase::generate(Ending::Kpk)),
+            Ending::Krk => KRK.get_or_init(|| Bitbase::generate(Ending::Krk)),
+            Ending::Kqk => KQK.get_or_init(|| Bitbase::generate(Ending::Kqk)),
+        }
+    }
+}
+
+/// One bit per position: set when the side with the extra piece wins with best play.
+/// Positions are stored with the strong side as White; Black's are mirrored onto them.
+struct Bitbase {
+    wins: Vec<u64>,
+}
+
+impl Bitbase {
+    fn is_win(&self, idx: usize) -> bool {
+        self.wins[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    /// Retrograde analysis: every legal position is played out one ply with `all_moves`,
+    /// then wins are propagated backwards until nothing changes. Whatever is not won by
+    /// then is a draw.
+    fn generate(ending: Ending) -> Bitbase {
+        let mut offsets = vec![0u32; TABLE_SIZE + 1];
+        let mut successors = Vec::new();
+        let mut legal = vec![false; TABLE_SIZE];
+
+        for kings in 0..TABLE_SIZE / 64 {
+            let (weak_to_move, strong_king, weak_king) = (kings >= 64 * 64, (kings / 64 % 64) as u8, (kings % 64) as u8);
+            let base = kings * 64;
+            let Some((mut pos, mut piece_sq)) = template(ending, weak_to_move, strong_king, weak_king) else {
+                offsets[base + 1..=base + 64].fill(successors.len() as u32);
+                continue;
+            };
+
+            for sq in 0..64u8 {
+                let idx = base + sq as usize;
+                let free = sq != strong_king && sq != weak_king;
+                if free && (ending != Ending::Kpk || (8..56).contains(&sq)) {
+                    pos.relocate_piece(piece_sq, sq);
+                    piece_sq = sq;
+
+                    // the side that just moved must not be left in check
+                    let mover = !pos.side_to_move();
+                    if !pos.square_under_attack(pos.king_square(mover), pos.side_to_move()) {
+                        legal[idx] = true;
+                        let moves = all_moves(&pos);
+                        if moves.is_empty() && pos.in_check() {
+                            successors.push(SUCC_WIN);  // the weak king is mated
+                        }
+                        for mv in moves.iter() {
+                            pos.do_move(mv);
+                            successors.push(successor(ending, &pos));
+                            pos.undo_move();
+                        }
+                    }
+                }
+                offsets[idx + 1] = successors.len() as u32;
+            }
+        }
+
+        let mut win = vec![false; TABLE_SIZE];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for idx in 0..TABLE_SIZE {
+                if !legal[idx] || win[idx] {
+                    continue;
+                }
+                let succ = &successors[offsets[idx] as usize..offsets[idx + 1] as usize];
+                let won = |&s: &u32| s == SUCC_WIN || (s != SUCC_DRAW && win[s as usize]);
+                let strong_to_move = idx < TABLE_SIZE / 2;
+                let now_won = if strong_to_move {
+                    succ.iter().any(won)
+                } else {
+                    !succ.is_empty() && succ.iter().all(won)
+                };
+                if now_won {
+                    win[idx] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        let mut wins = vec![0u64; TABLE_SIZE / 64];
+        for idx in (0..TABLE_SIZE).filter(|&idx| win[idx]) {
+            wins[idx / 64] |= 1 << (idx % 64);
+        }
+        Bitbase { wins }
+    }
+}
+
+fn index(weak_to_move: bool, strong_king: u8, weak_king: u8, piece: u8) -> usize {
+    ((weak_to_move as usize * 64 + strong_king as usize) * 64 + weak_king as usize) * 64 + piece as usize
+}
+
+/// A position with both kings placed and the piece on some free square, or `None` if the
+/// kings touch. The piece is then walked over the board with `relocate_piece`.
+fn template(ending: Ending, weak_to_move: bool, strong_king: u8, weak_king: u8) -> Option<(Position, u8)> {
+    let apart = (weak_king % 8).abs_diff(strong_king % 8) > 1 || (weak_king / 8).abs_diff(strong_king / 8) > 1;
+    if !apart {
+        return None;
+    }
+    let piece_sq = (8..56).find(|&sq| sq != strong_king && sq != weak_king)?;
+
+    let mut board = ['.'; 64];
+    board[strong_king as usize] = 'K';
+    board[weak_king as usize]   = 'k';
+    board[piece_sq as usize]    = ending.piece().piece_initial().to_ascii_uppercase();
+    let ranks: Vec<String> = (0..8).rev().map(|rank| {
+        let mut row = String::new();
+        let mut empty = 0;
+        for &ch in &board[rank * 8..rank * 8 + 8] {
+            if ch == '.' {
+                empty += 1;
+                continue;
+            }
+            if empty > 0 { row.push_str(&empty.to_string()); empty = 0; }
+            row.push(ch);
+        }
+        if empty > 0 { row.push_str(&empty.to_string()); }
+        row
+    }).collect();
+    let fen = format!("{} {} - - 0 1", ranks.join("/"), if weak_to_move { 'b' } else { 'w' });
+    Some((Position::load_position_from_fen(&fen), piece_sq))
+}
+
+/// Where a move of the table being built leads: back into it, or (after a capture or a
+/// promotion) to a result that is already known.
+fn successor(ending: Ending, pos: &Position) -> u32 {
+    match classify(pos) {
+        Some((to_ending, strong, idx)) if to_ending == ending => {
+            debug_assert!(strong.is_white());
+            idx as u32
+        }
+        // a pawn that promoted to a queen or rook: those tables are built first
+        Some((to_ending, _, idx)) => if to_ending.table().is_win(idx) { SUCC_WIN } else { SUCC_DRAW },
+        None => SUCC_DRAW,
+    }
+}
+
+/// The ending, strong side and table index of `pos`, if it is one of the covered endings.
+fn classify(pos: &Position) -> Option<(Ending, Color, usize)> {
+    if pos.occupied().count_ones() != 3 {
+        return None;
+    }
+    let (ending, strong) = [Ending::Kpk, Ending::Krk, Ending::Kqk].into_iter()
+        .flat_map(|ending| [Color::White, Color::Black].map(|color| (ending, color)))
+        .find(|&(ending, color)| pos.piece_bb(ending.piece(), color) != 0)?;
+
+    // mirror Black's positions so that the strong side is always White
+    let flip = |sq: u8| if strong.is_white() { sq } else { sq ^ 56 };
+    let piece_sq = pos.piece_bb(ending.piece(), strong).trailing_zeros() as u8;
+    let weak_to_move = pos.side_to_move().is_white() != strong.is_white();
+    let idx = index(weak_to_move, flip(pos.king_square(strong)), flip(pos.king_square(!strong)), flip(piece_sq));
+    Some((ending, strong, idx))
+}
+
+/// Whether the side with the extra piece wins, for KPK, KRK and KQK positions. Tables are
+/// generated the first time each ending is probed.
+pub fn probe(pos: &Position) -> Option<(Ending, Color, bool)> {
+    let (ending, strong, idx) = classify(pos)?;
+    Some((ending, strong, ending.table().is_win(idx)))
+}
+
+/// Builds all tables now rather than in the middle of the first search that needs them.
+pub fn init() {
+    for ending in [Ending::Kqk, Ending::Krk, Ending::Kpk] {
+        ending.table();
+    }
+}
+
+/// Exact evaluation (White's point of view) of a covered ending: 0 if drawn, otherwise
+/// [`KNOWN_WIN`] plus a gradient that drives the weak king to the edge, brings the strong
+/// king closer and pushes the pawn, so that the search can make progress.
+pub fn evaluate(pos: &Position) -> Option<i16> {
+    let (ending, strong, win) = probe(pos)?;
+    if !win {
+        return Some(0);
+    }
+
+    let strong_king = pos.king_square(strong);
+    let weak_king   = pos.king_square(!strong);
+    let distance = |a: u8, b: u8| (a % 8).abs_diff(b % 8).max((a / 8).abs_diff(b / 8)) as i16;
+    let edge = |sq: u8| {
+        let file = (sq % 8) as i16;
+        let rank = (sq / 8) as i16;
+        (2 * file - 7).abs().max((2 * rank - 7).abs()) / 2  // 0 in the centre, 3 on the edge
+    };
+
+    let mut score = KNOWN_WIN + 20 * edge(weak_king) + 10 * (7 - distance(strong_king, weak_king));
+    if ending == Ending::Kpk {
+        let pawn = pos.pawns(strong).trailing_zeros() as u8;
+        let rank = if strong.is_white() { pawn / 8 } else { 7 - pawn / 8 };
+        score += 50 * rank as i16;
+    }
+    Some(if strong.is_white() { score } else { -score })
+}
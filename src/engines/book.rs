@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use rand::Rng;
+use crate::color::Color;
+use crate::mov::Move;
+use crate::pgn::{self, Game, PgnError};
+use crate::piece::{is_empty, to_color, to_piece, Piece};
+use crate::position::{square_name, Position, Status, NO_SQ};
+use crate::tables::PAWN_ATTACKS;
+use crate::tables::polyglot::RANDOM64;
+
+const ENTRY_BYTES: usize = 16;
+const CASTLING_KEYS: usize = 768;
+const EN_PASSANT_KEYS: usize = 772;
+const TURN_KEY: usize = 780;
+
+
+impl Position {
+    /// The Polyglot hash of this position, used to look it up in `.bin` opening books.
+    /// Unlike [`Position::zobrist`], the en passant file only counts when a pawn of the side
+    /// to move can actually capture there.
+    pub fn polyglot_key(&self) -> u64 {
+        let mut key = 0u64;
+
+        for color in [Color::White, Color::Black] {
+            for piece in 0..6 {
+                // kinds are ordered black pawn, white pawn, black knight, ...
+                let kind = 2 * piece + color.is_white() as usize;
+                let mut bb = self.piece_bb(Piece::from(piece), color);
+                while bb != 0 {
+                    let sq = bb.trailing_zeros() as usize;
+                    key ^= RANDOM64[64 * kind + sq];
+                    bb &= bb - 1;
+                }
+            }
+        }
+
+        let rights = [
+            self.kingside(Color::White),
+            self.queenside(Color::White),
+            self.kingside(Color::Black),
+            self.queenside(Color::Black),
+        ];
+        for (i, _) in rights.iter().enumerate().filter(|(_, has)| **has) {
+            key ^= RANDOM64[CASTLING_KEYS + i];
+        }
+
+        let ep = self.en_passant();
+        let us = self.side_to_move();
+        if ep != NO_SQ && PAWN_ATTACKS[!us as usize][ep as usize] & self.pawns(us) != 0 {
+            key ^= RANDOM64[EN_PASSANT_KEYS + (ep % 8) as usize];
+        }
+
+        if us.is_white() {
+            key ^= RANDOM64[TURN_KEY];
+        }
+        key
+    }
+}
+
+
+/// One record of a Polyglot book. `mv` is in Polyglot's own encoding; see [`Book::moves`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BookEntry {
+    pub key:    u64,
+    pub mv:     u16,
+    pub weight: u16,
+    pub learn:  u32,
+}
+
+impl BookEntry {
+    fn from_bytes(bytes: &[u8]) -> BookEntry {
+        BookEntry {
+            key:    u64::from_be_bytes(bytes[0..8].try_into().unwrap()),
+            mv:     u16::from_be_bytes(bytes[8..10].try_into().unwrap()),
+            weight: u16::from_be_bytes(bytes[10..12].try_into().unwrap()),
+            learn:  u32::from_be_bytes(bytes[12..16].try_into().unwrap()),
+        }
+    }
+
+    pub fn to_bytes(self) -> [u8; ENTRY_BYTES] {
+        let mut bytes = [0; ENTRY_BYTES];
+        bytes[0..8].copy_from_slice(&self.key.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.mv.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.weight.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.learn.to_be_bytes());
+        bytes
+    }
+}
+
+/// How a move is picked when the book has several for a position.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum BookSelection {
+    /// At random, in proportion to the entry weights.
+    #[default]
+    Weighted,
+    /// Always the highest weight.
+    Best,
+}
+
+/// A Polyglot `.bin` opening book held in memory.
+#[derive(Clone, Debug, Default)]
+pub struct Book {
+    entries: Vec<BookEntry>,  // sorted by key
+    pub selection: BookSelection,
+    pub max_depth: Option<u16>,  // in plies from the start of the game
+}
+
+impl Book {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Book> {
+        Ok(Self::from_bytes(&fs::read(path)?))
+    }
+
+    /// Reads 16-byte big-endian records; a trailing partial record is ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Book {
+        let mut entries: Vec<BookEntry> = bytes.chunks_exact(ENTRY_BYTES).map(BookEntry::from_bytes).collect();
+        // Polyglot books are sorted already; this makes hand-made ones work too
+        entries.sort_by_key(|e| e.key);
+        Book { entries, ..Book::default() }
+    }
+
+    pub fn from_entries(mut entries: Vec<BookEntry>) -> Book {
+        entries.sort_by_key(|e| e.key);
+        Book { entries, ..Book::default() }
+    }
+
+    pub fn entries(&self) -> &[BookEntry] {
+        &self.entries
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.entries.iter().flat_map(|e| e.to_bytes()).collect()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The legal book moves for `pos` with their weights, most popular first.
+    /// Entries that do not decode to a legal move are skipped.
+    pub fn moves(&self, pos: &Position) -> Vec<(Move, u16)> {
+        let key   = pos.polyglot_key();
+        let start = self.entries.partition_point(|e| e.key < key);
+        let mut moves: Vec<(Move, u16)> = self.entries[start..]
+            .iter()
+            .take_while(|e| e.key == key)
+            .filter_map(|e| Some((decode_move(pos, e.mv)?, e.weight)))
+            .collect();
+        moves.sort_by_key(|&(_, weight)| std::cmp::Reverse(weight));
+        moves
+    }
+
+    /// A book move for `pos`, or `None` when out of book or past `max_depth`.
+    pub fn probe<R: Rng>(&self, pos: &Position, rng: &mut R) -> Option<Move> {
+        if self.max_depth.is_some_and(|max| game_ply(pos) >= max) {
+            return None;
+        }
+        let moves = self.moves(pos);
+        match self.selection {
+            BookSelection::Best => moves.first().map(|&(mv, _)| mv),
+            BookSelection::Weighted => {
+                let total: u32 = moves.iter().map(|&(_, w)| w as u32).sum();
+                if total == 0 {
+                    return moves.first().map(|&(mv, _)| mv);
+                }
+                let mut pick = rng.random_range(0..total);
+                moves.into_iter().find(|&(_, w)| {
+                    if pick < w as u32 { return true; }
+                    pick -= w as u32;
+                    false
+                }).map(|(mv, _)| mv)
+            }
+        }
+    }
+}
+
+
+/// Results of the games in which one move was played from one position, from the point of
+/// view of the side that played it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MoveStats {
+    pub wins:   u32,
+    pub draws:  u32,
+    pub losses: u32,
+}
+
+impl MoveStats {
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Share of the points scored, from 0.0 to 1.0.
+    pub fn score(&self) -> f32 {
+        (self.wins as f32 + self.draws as f32 / 2.0) / self.games().max(1) as f32
+    }
+
+    /// Polyglot's usual weight: two per win and one per draw.
+    fn weight(&self) -> u32 {
+        2 * self.wins + self.draws
+    }
+}
+
+/// Collects win/draw/loss counts per (position, move) from finished games and turns the
+/// frequent, successful ones into a Polyglot book.
+#[derive(Clone, Debug)]
+pub struct BookBuilder {
+    stats: HashMap<(u64, u16), MoveStats>,  // (polyglot key, polyglot move)
+    pub max_ply:   usize,   // only the first `max_ply` half-moves of each game are counted
+    pub min_games: u32,     // moves played in fewer games are left out
+    pub min_score: f32,     // moves scoring less than this share of the points are left out
+}
+
+impl Default for BookBuilder {
+    fn default() -> Self {
+        BookBuilder { stats: HashMap::new(), max_ply: 30, min_games: 1, min_score: 0.0 }
+    }
+}
+
+impl BookBuilder {
+    pub fn new() -> BookBuilder {
+        BookBuilder::default()
+    }
+
+    /// Counts the opening of `game`. Unfinished games (`*`) are skipped; returns whether
+    /// the game was used.
+    pub fn add_game(&mut self, game: &Game) -> bool {
+        let winner = match game.result {
+            Status::Ongoing => return false,
+            Status::Checkmate(color) => Some(color),
+            Status::Draw => None,
+        };
+
+        let mut pos = game.start.clone();
+        for &mv in game.moves.iter().take(self.max_ply) {
+            let mover = pos.side_to_move();
+            let entry = self.stats.entry((pos.polyglot_key(), encode_move(mv))).or_default();
+            match winner {
+                None => entry.draws += 1,
+                Some(color) if color as u8 == mover as u8 => entry.wins += 1,
+                Some(_) => entry.losses += 1,
+            }
+            pgn::play(&mut pos, mv);
+        }
+        true
+    }
+
+    /// Parses a whole PGN file and counts its games. Returns how many were used.
+    pub fn add_pgn(&mut self, text: &str) -> Result<usize, PgnError> {
+        let games = pgn::parse_pgn(text)?;
+        Ok(games.iter().filter(|game| self.add_game(game)).count())
+    }
+
+    /// Statistics for one move, keyed by the position before it.
+    pub fn stats(&self, pos: &Position, mv: Move) -> MoveStats {
+        self.stats.get(&(pos.polyglot_key(), encode_move(mv))).copied().unwrap_or_default()
+    }
+
+    /// The book from everything counted so far, with the filters applied. Weights are
+    /// scaled down together if the largest would not fit in 16 bits.
+    pub fn build(&self) -> Book {
+        let kept: Vec<(u64, u16, u32)> = self.stats.iter()
+            .filter(|(_, s)| s.games() >= self.min_games && s.score() >= self.min_score)
+            .map(|(&(key, mv), s)| (key, mv, s.weight()))
+            .collect();
+
+        let max = kept.iter().map(|&(_, _, w)| w).max().unwrap_or(0);
+        let scale = |w: u32| if max > u16::MAX as u32 { (w as u64 * u16::MAX as u64 / max as u64) as u16 } else { w as u16 };
+
+        let mut entries: Vec<BookEntry> = kept.into_iter()
+            .map(|(key, mv, weight)| BookEntry { key, mv, weight: scale(weight), learn: 0 })
+            .collect();
+        // same order as Polyglot's own tool: by key, best move first
+        entries.sort_by_key(|e| (e.key, std::cmp::Reverse(e.weight), e.mv));
+        Book::from_entries(entries)
+    }
+}
+
+/// Plies played since the start of the game, from the full-move counter (which an
+/// unchecked FEN may leave at 0).
+fn game_ply(pos: &Position) -> u16 {
+    pos.full_move().saturating_sub(1).saturating_mul(2).saturating_add(pos.side_to_move().is_black() as u16)
+}
+
+/// Polyglot moves: to file/rank in bits 0-5, from file/rank in bits 6-11, promotion piece
+/// (1 = knight .. 4 = queen) in bits 12-14. Castling is written as the king taking its rook.
+fn decode_move(pos: &Position, mv: u16) -> Option<Move> {
+    let from = ((mv >> 6) & 0x3F) as u8;
+    let mut to = (mv & 0x3F) as u8;
+
+    let (mover, target) = (pos.colored_piece_at_sq(from), pos.colored_piece_at_sq(to));
+    if to_piece(mover) == Piece::King && !is_empty(target) && to_piece(target) == Piece::Rook
+        && to_color(target) as u8 == to_color(mover) as u8 {
+        to = if to > from { from + 2 } else { from - 2 };
+    }
+
+    let mut uci = format!("{}{}", square_name(from), square_name(to));
+    match (mv >> 12) & 0x7 {
+        0 => {}
+        1 => uci.push('n'),
+        2 => uci.push('b'),
+        3 => uci.push('r'),
+        4 => uci.push('q'),
+        _ => return None,
+    }
+    pos.parse_uci_move(&uci).ok()
+}
+
+/// Inverse of [`decode_move`].
+fn encode_move(mv: Move) -> u16 {
+    let from = mv.from() as u16;
+    let to = match mv {
+        mv if mv.is_king_castle()  => from + 3,
+        mv if mv.is_queen_castle() => from - 4,
+        mv => mv.to() as u16,
+    };
+    let promotion = if mv.is_promotion() {
+        match mv.promotion_piece() {
+            Piece::Knight => 1,
+            Piece::Bishop => 2,
+            Piece::Rook   => 3,
+            _             => 4,
+        }
+    } else {
+        0
+    };
+    promotion << 12 | from << 6 | to
+}
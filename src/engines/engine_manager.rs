@@ -1,19 +1,31 @@
 use std::cmp::PartialEq;
 use std::fmt;
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
 use std::time::{Duration, Instant};
 use crate::attacks::movegen::all_moves;
 use crate::color::Color;
 use crate::engines::constants::MAX_DEPTH;
-use crate::engines::engine_manager::Eval::{Basic, WithTradingBonus};
+use crate::engines::engine_manager::Eval::{Basic, Nnue, WithKingSafety, WithMobility, WithPawnStructure, WithTradingBonus};
 use crate::engines::engine_manager::Search::{AlphaBeta, Minimax, Random, CaptureLastPieceMO, WithHashMoveOrdering, WithMVVLVAMoveOrdering, WithNullMovePruning, WithQuiescenceSearch, WithRootPVOrdering, WithTranspositionTable, WithHistoryHeuristic, WithKillerMoves, WithLMR, WithInCheckQuiescence, Simplified1, Simplified2, Simplified3, Testing, Simplified4, Simplified5, Simplified6, Simplified7, Simplified8, Simplified9, Simplified10, Simplified11, Simplified12, Simplified13, Simplified14, Simplified15};
-use crate::engines::evaluate::{e1, e2};
+use crate::engines::bitbase;
+use crate::engines::evaluate::{e1, e2, e4, e5, e6, e7};
 use crate::engines::search::{s1, s10, s11, s12, s13, s14, s2, s3, s4, s5, s6, s7, s8, s9, simplified1, simplified10, simplified11, simplified12, simplified13, simplified14, simplified15, simplified2, simplified3, simplified4, simplified5, simplified6, simplified7, simplified8, simplified9, testing_only};
+use crate::engines::book::Book;
+use crate::engines::nnue::Network;
+use crate::engines::search_params::SearchParams;
+use crate::engines::tablebase::{self, Tablebase};
 use crate::engines::transposition_table::TransTable;
+pub use crate::engines::transposition_table::DEFAULT_HASH_MB;
 use crate::mov::Move;
 use crate::position::Position;
 use crate::engines::history::History;
 use crate::engines::pv::PV;
+use crate::engines::score::Score;
 use crate::engines::stats::Stats;
+use crate::engines::time_manager::{Clock, TimeManager, DEFAULT_MOVE_OVERHEAD};
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Search {
@@ -59,9 +71,13 @@ pub const NUMBER_OF_SEARCH_ALGORITHMS: u8 = 28;
 pub enum Eval {
     Basic,
     WithTradingBonus, // awards trading in winning positions, punishes in losing positions.
+    WithPawnStructure, // doubled, isolated, backward, connected and passed pawns, cached in a pawn hash.
+    WithKingSafety, // weighted attacks into the king zone, pawn shield and open files near the king.
+    WithMobility, // safe squares per piece, bishop pair, rook files and 7th rank, outposts, trapped pieces.
+    Nnue, // the network set with `Engine::set_network`; WithMobility until there is one.
 }
 
-pub const NUMBER_OF_EVAL_ALGORITHMS: u8 = 2;
+pub const NUMBER_OF_EVAL_ALGORITHMS: u8 = 6;
 
 impl TryFrom<u8> for Search {
     type Error = ();
@@ -109,6 +125,10 @@ impl TryFrom<u8> for Eval {
         match x {
             1 => Ok(Basic),
             2 => Ok(WithTradingBonus),
+            3 => Ok(WithPawnStructure),
+            4 => Ok(WithKingSafety),
+            5 => Ok(WithMobility),
+            6 => Ok(Nnue),
             _ => Err(()),
         }
     }
@@ -119,7 +139,7 @@ const PV_ARRAY_LENGTH: usize = ((MAX_DEPTH*MAX_DEPTH + MAX_DEPTH)/2) as usize; /
 
 
 pub struct Ctx {
-    pub tt:             TransTable,
+    pub tt:             Arc<TransTable>,  // shared by all threads of one engine
     pub eval_fn:        fn(&Position) -> i16,
     pub nodes:          u64,
     pub total_nodes:    u64,
@@ -131,12 +151,22 @@ pub struct Ctx {
     pub stats:          Stats,
     pub history:        History,
     pub killers:        [[Move; 2]; MAX_DEPTH as usize],
+    pub stop:           Arc<AtomicBool>,
+    pub node_limit:     Option<u64>,
+    pub node_counter:   Arc<AtomicU64>,   // `nodes`, readable from other threads (lags a little)
+    pub tablebase:      Option<Arc<Tablebase>>,
+    pub search_params:  SearchParams,  // only simplified15 reads these
 }
 
 impl Ctx {
     pub fn new(eval_fn: fn(&Position) -> i16) -> Self {
+        Self::with_tt(eval_fn, Arc::new(TransTable::new(DEFAULT_HASH_MB)))
+    }
+
+    /// A context of its own that probes and fills an existing table.
+    pub fn with_tt(eval_fn: fn(&Position) -> i16, tt: Arc<TransTable>) -> Self {
         Ctx {
-            tt:             TransTable::new(128),
+            tt,
             eval_fn,
             nodes:          0,
             total_nodes:    0,
@@ -148,7 +178,25 @@ impl Ctx {
             stats:          Stats::default(),
             history:        Default::default(),
             killers:        [[Move::null(); 2]; MAX_DEPTH as usize],
+            stop:           Arc::new(AtomicBool::new(false)),
+            node_limit:     None,
+            node_counter:   Arc::new(AtomicU64::new(0)),
+            tablebase:      None,
+            search_params:  SearchParams::default(),
+        }
+    }
+
+    /// True once the search has to give up: the deadline has passed, the node budget is
+    /// spent or someone raised the stop flag. A `None` deadline never passes.
+    /// As this runs at every node, it also publishes `nodes` every 1024 nodes.
+    #[inline(always)]
+    pub fn should_stop(&self, deadline: Option<Instant>) -> bool {
+        if self.nodes.is_multiple_of(1024) {
+            self.node_counter.store(self.nodes, Ordering::Relaxed);
         }
+        deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            || self.node_limit.is_some_and(|limit| self.nodes >= limit)
+            || self.stop.load(Ordering::Relaxed)
     }
 }
 
@@ -159,17 +207,58 @@ type SearchFn = fn(
     i16,                     // beta
     i16,                     // alpha
     i16,                      // color factor
-    Instant,                  // deadline
+    Option<Instant>,          // deadline (`None` searches until stopped)
     &mut Ctx)           // shared context
     -> Option<(i16, Move)>;   // score + best move
 
 
+/// Snapshot of the search after a completed iterative-deepening iteration.
+#[derive(Clone, Debug)]
+pub struct SearchInfo {
+    pub depth:   u8,
+    pub score:   Score,         // side-to-move perspective
+    pub nodes:   u64,
+    pub elapsed: Duration,
+    pub hashfull: u16,          // permille
+    pub pv:      Vec<Move>,
+}
+
+impl SearchInfo {
+    pub fn nps(&self) -> u64 {
+        let micros = self.elapsed.as_micros().max(1);
+        (self.nodes as u128 * 1_000_000 / micros) as u64
+    }
+}
+
+type InfoCallback = Box<dyn FnMut(&SearchInfo) + Send>;
+
+
+/// What, besides the engine's time budget, ends a search. Unset fields do not limit it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    pub depth:    Option<u8>,   // stop once this depth has been completed
+    pub nodes:    Option<u64>,
+    pub movetime: Option<u64>,  // milliseconds, overrides the clock / time per move
+    pub infinite: bool,         // no time limit at all; runs until another limit or the stop flag
+    pub mate:     Option<u8>,   // stop once a mate in at most this many moves is found
+}
+
+
 pub struct Engine {
     search: Search,
     eval: Eval,
     search_fn:  SearchFn,
     search_ctx: Ctx,
     time_ms:    u64,
+    clock:      Option<Clock>,
+    move_overhead: u64,
+    limits:     SearchLimits,
+    on_info:    Option<InfoCallback>,
+    hash_mb:    usize,
+    helpers:    Vec<Ctx>,          // one per extra Lazy SMP thread
+    helpers_stop: Arc<AtomicBool>, // raised by the main thread when it is done
+    book:       Option<Arc<Book>>,
+    network:    Option<Arc<Network>>,
 }
 
 impl Engine {
@@ -185,13 +274,27 @@ impl Engine {
             search_fn,
             search_ctx: Ctx::new(eval_fn),
             time_ms,
+            clock:      None,
+            move_overhead: DEFAULT_MOVE_OVERHEAD,
+            limits:     SearchLimits::default(),
+            on_info:    None,
+            hash_mb:    DEFAULT_HASH_MB,
+            helpers:    Vec::new(),
+            helpers_stop: Arc::new(AtomicBool::new(false)),
+            book:       None,
+            network:    None,
         }
     }
 
     fn eval_fn(eval: Eval) -> fn(&Position) -> i16 {
+        // the bitbases know their endings exactly: probed once here, not in every eval layer
         match eval {
-            Basic => e1::evaluate,
-            WithTradingBonus => e2::evaluate,
+            Basic => |pos| bitbase::evaluate(pos).unwrap_or_else(|| e1::evaluate(pos)),
+            WithTradingBonus => |pos| bitbase::evaluate(pos).unwrap_or_else(|| e2::evaluate(pos)),
+            WithPawnStructure => |pos| bitbase::evaluate(pos).unwrap_or_else(|| e4::evaluate(pos)),
+            WithKingSafety => |pos| bitbase::evaluate(pos).unwrap_or_else(|| e5::evaluate(pos)),
+            WithMobility => |pos| bitbase::evaluate(pos).unwrap_or_else(|| e6::evaluate(pos)),
+            Nnue => |pos| bitbase::evaluate(pos).unwrap_or_else(|| e7::evaluate(pos)),
         }
     }
 
@@ -234,14 +337,158 @@ impl Engine {
     }
 
     pub fn total_nodes(&self) -> u64 {
-        self.search_ctx.total_nodes
+        self.search_ctx.total_nodes + self.helpers.iter().map(|h| h.total_nodes).sum::<u64>()
+    }
+
+    /// Nodes of the last search, summed over all threads.
+    pub fn nodes(&self) -> u64 {
+        self.search_ctx.nodes + self.helpers.iter().map(|h| h.nodes).sum::<u64>()
+    }
+
+    /// Number of search threads. Extra threads run Lazy SMP: they search the same root
+    /// and share the transposition table, but keep their own history, killers and PV.
+    pub fn set_threads(&mut self, threads: usize) {
+        let eval_fn = self.search_ctx.eval_fn;
+        let helpers = threads.max(1) - 1;
+        self.helpers.truncate(helpers);
+        while self.helpers.len() < helpers {
+            let mut helper = Ctx::with_tt(eval_fn, Arc::clone(&self.search_ctx.tt));
+            helper.stop = Arc::clone(&self.helpers_stop);
+            helper.tablebase = self.search_ctx.tablebase.clone();
+            helper.search_params = self.search_ctx.search_params;
+            self.helpers.push(helper);
+        }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.helpers.len() + 1
+    }
+
+    /// Reallocates the transposition table with `mb` megabytes (at least 1). Its contents are lost.
+    pub fn set_hash_mb(&mut self, mb: usize) {
+        self.hash_mb = mb.max(1);
+        let tt = Arc::new(TransTable::new(self.hash_mb));
+        for ctx in std::iter::once(&mut self.search_ctx).chain(&mut self.helpers) {
+            ctx.tt = Arc::clone(&tt);
+        }
+    }
+
+    pub fn hash_mb(&self) -> usize {
+        self.hash_mb
+    }
+
+    /// Forgets everything in the transposition table, e.g. before a new game.
+    pub fn clear_hash(&mut self) {
+        self.search_ctx.tt.clear();
+    }
+
+    /// Permille of the transposition table filled by the last search.
+    pub fn hashfull(&self) -> u16 {
+        self.search_ctx.tt.hashfull(self.search_ctx.generation)
+    }
+
+    /// Loads Syzygy tablebases from `path` (several directories separated by `:`, or `;` on
+    /// Windows) for the search to probe. An empty path unloads them. Returns the largest
+    /// number of pieces covered.
+    pub fn set_syzygy_path(&mut self, path: &str) -> io::Result<usize> {
+        let tablebase = match path.trim() {
+            "" => None,
+            path => Some(Arc::new(Tablebase::open(path)?)),
+        };
+        let max_pieces = tablebase.as_ref().map_or(0, |tb| tb.max_pieces());
+        for ctx in std::iter::once(&mut self.search_ctx).chain(&mut self.helpers) {
+            ctx.tablebase = tablebase.clone();
+        }
+        Ok(max_pieces)
+    }
+
+    pub fn tablebase(&self) -> Option<&Tablebase> {
+        self.search_ctx.tablebase.as_deref()
+    }
+
+    /// Opening book consulted by [`Engine::pick`] before searching. Clones share it.
+    pub fn set_book(&mut self, book: Option<Book>) {
+        self.book = book.map(Arc::new);
+    }
+
+    pub fn book(&self) -> Option<&Book> {
+        self.book.as_deref()
+    }
+
+    /// Margins and reductions of simplified15, for every thread.
+    pub fn set_search_params(&mut self, params: SearchParams) {
+        self.search_ctx.search_params = params;
+        self.helpers.iter_mut().for_each(|helper| helper.search_params = params);
+    }
+
+    pub fn search_params(&self) -> SearchParams {
+        self.search_ctx.search_params
+    }
+
+    /// NNUE weights for the `Nnue` eval; searched positions get them attached. Clones share them.
+    pub fn set_network(&mut self, network: Option<Arc<Network>>) {
+        self.network = network;
+    }
+
+    pub fn network(&self) -> Option<&Arc<Network>> {
+        self.network.as_ref()
+    }
+
+    /// Gives `pos` this engine's network (or none, for the other evals) unless it already has it.
+    fn attach_network(&self, pos: &mut Position) {
+        let wanted = if matches!(self.eval, Nnue) { self.network.as_ref() } else { None };
+        if wanted.map(Arc::as_ptr) != pos.network().map(Arc::as_ptr) {
+            pos.set_network(wanted.cloned());
+        }
+    }
+
+    /// A move from the opening book for `pos`, if there is a book and it knows the position.
+    pub fn book_move(&self, pos: &Position) -> Option<Move> {
+        self.book.as_ref()?.probe(pos, &mut rand::rng())
     }
 
+    /// Search every move for exactly `ms` milliseconds. Replaces any clock set before.
     pub fn set_time_limit(&mut self, ms: u64) {
         self.time_ms = ms;
+        self.clock   = None;
+    }
+
+    /// Budget the next searches from a game clock instead of a fixed time per move.
+    pub fn set_clock(&mut self, clock: Option<Clock>) {
+        self.clock = clock;
+    }
+
+    pub fn clock(&self) -> Option<Clock> {
+        self.clock
+    }
+
+    /// Milliseconds of every clock budget kept back for communication lag.
+    pub fn set_move_overhead(&mut self, ms: u64) {
+        self.move_overhead = ms;
     }
 
-    pub fn pick_and_stats(&mut self, pos: &mut Position) -> (Move, u8, i16) {
+    /// A node limit counts the nodes of the main thread only.
+    pub fn set_limits(&mut self, limits: SearchLimits) {
+        self.limits = limits;
+    }
+
+    pub fn limits(&self) -> SearchLimits {
+        self.limits
+    }
+
+    /// Flag that, once raised, aborts the running search; the best move of the last completed
+    /// iteration is returned. The flag is not lowered by the engine: whoever starts the next
+    /// search has to do that.
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.search_ctx.stop)
+    }
+
+    /// Called after every completed iteration of `pick_and_stats`.
+    pub fn set_info_callback(&mut self, callback: impl FnMut(&SearchInfo) + Send + 'static) {
+        self.on_info = Some(Box::new(callback));
+    }
+
+    pub fn pick_and_stats(&mut self, pos: &mut Position) -> (Move, u8, Score) {
         // ───────────────────────────────────────────────────────────────
         // (0) fresh bookkeeping for this whole search
         // ───────────────────────────────────────────────────────────────
@@ -249,69 +496,164 @@ impl Engine {
         if pos.undo_stack.is_near_full() {
             pos.undo_stack.make_space();
         }
+        self.attach_network(pos);
         let ctx = &mut self.search_ctx;
         Self::reset_ctx(ctx);
 
-        let deadline = Instant::now() + Duration::from_millis(self.time_ms);
+        // in a tablebase ending the DTZ tables already know the move that makes progress
+        if let Some(probe) = ctx.tablebase.as_ref().and_then(|tb| tb.probe_root(pos)) {
+            let score = Score::from_search(tablebase::wdl_score(probe.wdl, 0));
+            if let Some(on_info) = self.on_info.as_mut() {
+                on_info(&SearchInfo {
+                    depth:   1,
+                    score,
+                    nodes:   0,
+                    elapsed: Duration::ZERO,
+                    hashfull: ctx.tt.hashfull(ctx.generation),
+                    pv:      vec![probe.mv],
+                });
+            }
+            return (probe.mv, 1, score);
+        }
+
+        ctx.node_limit = self.limits.nodes;
+
+        let mut time = if self.limits.infinite {
+            TimeManager::infinite()
+        } else if let Some(movetime) = self.limits.movetime {
+            TimeManager::fixed(movetime)
+        } else if let Some(clock) = self.clock {
+            TimeManager::new(clock, self.move_overhead)
+        } else {
+            TimeManager::fixed(self.time_ms)
+        };
+        let deadline = time.deadline();
         let color    = if pos.side_to_move() == Color::White { 1 } else { -1 };
+        let max_depth = self.limits.depth.unwrap_or(MAX_DEPTH as u8 - 2).min(MAX_DEPTH as u8 - 2);
+
+        let search_fn    = self.search_fn;
+        let helpers_stop = &self.helpers_stop;
+        let helper_nodes: Vec<Arc<AtomicU64>> = self.helpers.iter().map(|h| Arc::clone(&h.node_counter)).collect();
+        helpers_stop.store(false, Ordering::Relaxed);
+
+        let (mut best, completed, best_eval) = thread::scope(|scope| {
+            for (i, helper) in self.helpers.iter_mut().enumerate() {
+                Self::reset_ctx(helper);
+                helper.generation = ctx.generation;
+                let mut pos = pos.clone();
+                // every other helper starts one ply deeper, so the threads spread over two depths
+                let first_depth = 1 + (i % 2 == 0) as u8;
+                scope.spawn(move || Self::helper_search(search_fn, &mut pos, helper, first_depth, max_depth, color));
+            }
 
-        let mut best_eval = i16::MIN;
-        let mut best      = Move::null();
-        let mut depth     = 1u8;
-
-        while Instant::now() < deadline {
-
-            ctx.stats     = Stats::default();
-            ctx.pv_index  = 0;
-            ctx.ply       = 0;
-            ctx.pv_array[0] = Move::null();
-            ctx.pv.clear_node(ctx.ply);
-            ctx.killers.iter_mut().for_each(|slot| *slot = [Move::null(); 2]);
-
-            let search_result = {
-                (self.search_fn)(
-                    pos,
-                    depth,
-                    i16::MIN + 1,
-                    i16::MAX,
-                    color,
-                    deadline,
-                    ctx,
-                )
-            }; // ctx borrow ends here
-
-            // ----- 1b. handle result  (now we can borrow ctx immutably)
-            match search_result {
-                Some((eval, mv)) if !mv.is_null() => {
-                    best      = mv;
-                    best_eval = eval;
-                    //ctx.stats.print();
-
-                    // prepare next iteration
-                    if depth >= (MAX_DEPTH - 2) as u8 || Instant::now() >= deadline {
-                        break;
+            let mut best_eval = Score::default();
+            let mut best      = Move::null();
+            let mut depth     = 1u8;
+            let mut completed = 0u8;
+
+            while !ctx.should_stop(deadline) {
+                Self::reset_iteration(ctx);
+
+                let search_result = {
+                    search_fn(
+                        pos,
+                        depth,
+                        i16::MIN + 1,
+                        i16::MAX,
+                        color,
+                        deadline,
+                        ctx,
+                    )
+                }; // ctx borrow ends here
+
+                // ----- 1b. handle result  (now we can borrow ctx immutably)
+                match search_result {
+                    Some((eval, mv)) if !mv.is_null() => {
+                        best      = mv;
+                        best_eval = Score::from_search(eval);
+                        completed = depth;
+                        //ctx.stats.print();
+
+                        if let Some(on_info) = self.on_info.as_mut() {
+                            let helpers: u64 = helper_nodes.iter().map(|n| n.load(Ordering::Relaxed)).sum();
+                            on_info(&SearchInfo {
+                                depth,
+                                score:   best_eval,
+                                nodes:   ctx.nodes + helpers,
+                                elapsed: time.elapsed(),
+                                hashfull: ctx.tt.hashfull(ctx.generation),
+                                pv:      Self::root_pv(ctx, pos, mv, depth),
+                            });
+                        }
+
+                        // prepare next iteration
+                        let mate_found = matches!((best_eval, self.limits.mate), (Score::Mate(n), Some(limit)) if n > 0 && n <= limit as i16);
+                        if depth >= max_depth || mate_found || time.iteration_done(mv, eval) {
+                            break;
+                        }
+                        depth = depth.saturating_add(1);
                     }
-                    depth = depth.saturating_add(1);
-
-                    // reset root-level bookkeeping
-                    ctx.pv_index = 0;
-                    ctx.ply      = 0;
+                    _ => break, // out of time / nodes, stopped, or no legal move
                 }
-                _ => break, // timeout or search returned no legal move
             }
-        }
 
+            helpers_stop.store(true, Ordering::Relaxed);
+            (best, completed, best_eval)
+        });
+
+        // no iteration finished: any legal move beats none (still null without legal moves)
         if best.is_null() {
-            println!("buggin");
-            best = all_moves(pos).get(0);
+            best = all_moves(pos).iter().next().unwrap_or(Move::null());
+        }
+
+        (best, completed, best_eval)
+    }
+
+    /// Lazy SMP helper: iterative deepening on its own copy of the root, filling the shared
+    /// table until the main thread is done. Its own results are not used.
+    fn helper_search(search_fn: SearchFn, pos: &mut Position, ctx: &mut Ctx, first_depth: u8, max_depth: u8, color: i16) {
+        for depth in first_depth..=max_depth {
+            Self::reset_iteration(ctx);
+            let result = search_fn(pos, depth, i16::MIN + 1, i16::MAX, color, None, ctx);
+            ctx.node_counter.store(ctx.nodes, Ordering::Relaxed);
+            if result.is_none() {
+                break;
+            }
         }
+    }
+
+    fn reset_iteration(ctx: &mut Ctx) {
+        ctx.stats     = Stats::default();
+        ctx.pv_index  = 0;
+        ctx.ply       = 0;
+        ctx.pv_array[0] = Move::null();
+        ctx.pv.clear_node(ctx.ply);
+        ctx.killers.iter_mut().for_each(|slot| *slot = [Move::null(); 2]);
+    }
 
-        // depth now points one past the last *completed* ply
-        (best, depth.saturating_sub(1), best_eval)
+    /// The root PV from the last iteration, cut at the first move that is not legal.
+    fn root_pv(ctx: &Ctx, pos: &Position, best: Move, depth: u8) -> Vec<Move> {
+        let mut line = ctx.pv.line();
+        line.truncate(depth as usize);
+        if line.first() != Some(&best) {
+            return vec![best];
+        }
+        let mut replay = pos.clone();
+        let mut pv = Vec::with_capacity(line.len());
+        for mv in line {
+            if !all_moves(&replay).contains(mv) {
+                break;
+            }
+            replay.do_move(mv);
+            pv.push(mv);
+        }
+        pv
     }
 
     fn reset_ctx(ctx: &mut Ctx) {
         ctx.history.clear();   // reset history to 0s
+        ctx.node_limit = None;
+        ctx.node_counter.store(0, Ordering::Relaxed);
         ctx.generation = ctx.generation.wrapping_add(1);
         ctx.total_nodes += ctx.nodes;
         ctx.nodes = 0;
@@ -324,14 +666,27 @@ impl Engine {
 
     pub fn clone(&self) -> Engine {
         let eval_fn   = Self::eval_fn  (self.eval);
-        let ctx = Ctx::new(eval_fn);
-        Engine {
+        let mut ctx = Ctx::with_tt(eval_fn, Arc::new(TransTable::new(self.hash_mb)));
+        ctx.tablebase = self.search_ctx.tablebase.clone();
+        ctx.search_params = self.search_ctx.search_params;
+        let mut engine = Engine {
             search: self.search,
             eval: self.eval,
             search_fn: self.search_fn,
             search_ctx: ctx,
             time_ms: self.time_ms,
-        }
+            clock: self.clock,
+            move_overhead: self.move_overhead,
+            limits: self.limits,
+            on_info: None,
+            hash_mb: self.hash_mb,
+            helpers: Vec::new(),
+            helpers_stop: Arc::new(AtomicBool::new(false)),
+            book: self.book.clone(),
+            network: self.network.clone(),
+        };
+        engine.set_threads(self.threads());
+        engine
     }
 
 
@@ -358,11 +713,10 @@ impl Engine {
         // ───────────────────────────────────────────────────────────────
         // (0) fresh bookkeeping for this whole search
         // ───────────────────────────────────────────────────────────────
+        self.attach_network(pos);
         let ctx = &mut self.search_ctx;
         Self::reset_ctx(ctx);
 
-        const FAR_FUTURE_SECS: u64 = 1000 * 365 * 24 * 60 * 60; // ~1000 years
-        let deadline = Instant::now() + Duration::from_secs(FAR_FUTURE_SECS); //no deadline
         let color    = if pos.side_to_move() == Color::White { 1 } else { -1 };
 
         if let Some(search_result) = {
@@ -372,7 +726,7 @@ impl Engine {
                 i16::MIN + 1,
                 i16::MAX,
                 color,
-                deadline,
+                None,
                 ctx,
             )
         } {
@@ -388,11 +742,10 @@ impl Engine {
         if pos.undo_stack.is_near_full() {
             pos.undo_stack.make_space();
         }
+        self.attach_network(pos);
         let ctx = &mut self.search_ctx;
         Self::reset_ctx(ctx);
 
-        const FAR_FUTURE_SECS: u64 = 1000 * 365 * 24 * 60 * 60; // ~1000 years
-        let deadline = Instant::now() + Duration::from_secs(FAR_FUTURE_SECS); //no deadline
         let color    = if pos.side_to_move() == Color::White { 1 } else { -1 };
 
         (self.search_fn)(
@@ -401,17 +754,27 @@ impl Engine {
             i16::MIN + 1,
             i16::MAX,
             color,
-            deadline,
+            None,
             ctx,);
 
         ctx.nodes
     }
 
     pub fn pick(&mut self, pos: &Position) -> Move {
+        if let Some(mv) = self.book_move(pos) {
+            return mv;
+        }
         let mut position = pos.clone();
         self.pick_and_stats(&mut position).0
     }
 
+    /// Static evaluation of `pos` by this engine's eval, from White's point of view.
+    pub fn evaluate(&self, pos: &Position) -> i16 {
+        let mut pos = pos.clone();
+        self.attach_network(&mut pos);
+        (self.search_ctx.eval_fn)(&pos)
+    }
+
     pub fn name(&self) -> String{
         let mut s = "[search: ".to_owned();
         s += &*self.search.to_string();
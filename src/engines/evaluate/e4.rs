@@ -0,0 +1,8 @@
+use crate::engines::evaluate::pawns;
+use crate::position::Position;
+
+pub fn evaluate(pos: &Position) -> i16 {
+    let (mg, eg) = pawns::evaluate(pos);
+    let mg_phase = pos.mg_phase();
+    pos.evaluate_2() + ((mg * mg_phase + eg * (24 - mg_phase)) / 24) as i16
+}
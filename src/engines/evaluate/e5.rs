@@ -0,0 +1,8 @@
+use crate::engines::evaluate::{e4, king_safety};
+use crate::position::Position;
+
+pub fn evaluate(pos: &Position) -> i16 {
+    let (mg, eg) = king_safety::evaluate(pos);
+    let mg_phase = pos.mg_phase();
+    e4::evaluate(pos) + ((mg * mg_phase + eg * (24 - mg_phase)) / 24) as i16
+}
@@ -0,0 +1,8 @@
+use crate::engines::evaluate::{e5, mobility};
+use crate::position::Position;
+
+pub fn evaluate(pos: &Position) -> i16 {
+    let (mg, eg) = mobility::evaluate(pos);
+    let mg_phase = pos.mg_phase();
+    e5::evaluate(pos) + ((mg * mg_phase + eg * (24 - mg_phase)) / 24) as i16
+}
@@ -0,0 +1,94 @@
+use crate::attacks::sliding::{diagonal_attacks, orthogonal_attacks};
+use crate::bitboards::FILE_A;
+use crate::color::Color;
+use crate::eval::{Tapered, Terms, KING_ATTACK_INDEX, KING_FILE_INDEX, SHIELD_INDEX};
+use crate::piece::Piece;
+use crate::position::Position;
+use crate::tables::{KING_MOVES, KNIGHT_MOVES};
+
+/// Percentage of the attack weight that counts, by number of attackers: a lone piece
+/// rarely mates, each extra one makes the attack far more dangerous.
+const ATTACKER_SCALE: [i32; 8] = [0, 0, 50, 75, 88, 94, 97, 99];
+
+
+/// King safety (White's point of view) as separate middlegame and endgame scores: weighted
+/// attacks into each king zone, a missing or advanced pawn shield and open or semi-open
+/// files near the king.
+pub(crate) fn evaluate(pos: &Position) -> (i32, i32) {
+    let mut score = Tapered::default();
+    terms(pos, &mut score);
+    (score.mg, score.eg)
+}
+
+/// The terms one by one, as the tuner counts them.
+pub(crate) fn terms(pos: &Position, terms: &mut impl Terms) {
+    danger(pos, Color::White, terms);
+    danger(pos, Color::Black, terms);
+}
+
+/// How exposed `color`'s king is, as penalties for `color`.
+fn danger(pos: &Position, color: Color, terms: &mut impl Terms) {
+    let sign = if color.is_white() { -1 } else { 1 };
+    let king = pos.king_square(color);
+    let zone = king_zone(color, king);
+    let occupied = pos.occupied();
+
+    // attacked king-zone squares by knights, bishops, rooks and queens
+    let mut attackers = 0;
+    let mut hits = [0; 4];
+    for (i, piece) in [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen].into_iter().enumerate() {
+        let (squares, count) = pos.piece_list(piece, !color);
+        for &sq in &squares[..count] {
+            let sq = sq as usize;
+            let attacks = match piece {
+                Piece::Knight => KNIGHT_MOVES[sq],
+                Piece::Bishop => diagonal_attacks(sq, occupied),
+                Piece::Rook   => orthogonal_attacks(sq, occupied),
+                _             => diagonal_attacks(sq, occupied) | orthogonal_attacks(sq, occupied),
+            };
+            let zone_hits = (attacks & zone).count_ones() as i32;
+            if zone_hits > 0 {
+                attackers += 1;
+                hits[i] += zone_hits;
+            }
+        }
+    }
+    let scale = ATTACKER_SCALE[attackers.min(7)];
+    for (i, &hits) in hits.iter().enumerate() {
+        if hits > 0 {
+            terms.add_percent(KING_ATTACK_INDEX + i, sign * hits * scale);
+        }
+    }
+
+    let ours   = pos.pawns(color);
+    let theirs = pos.pawns(!color);
+    let king_file = (king % 8) as i32;
+    for file in (king_file - 1).max(0)..=(king_file + 1).min(7) {
+        let file_bb = FILE_A << file;
+        let shield = ours & file_bb & ranks_ahead(color, king, 2);
+        if shield == 0 {
+            terms.add(SHIELD_INDEX + 1, sign);
+        } else if shield & ranks_ahead(color, king, 1) == 0 {
+            terms.add(SHIELD_INDEX, sign);
+        }
+
+        if ours & file_bb == 0 {
+            terms.add(KING_FILE_INDEX + if theirs & file_bb == 0 { 1 } else { 0 }, sign);
+        }
+    }
+}
+
+/// The king's square, its neighbours and the three squares in front of those.
+fn king_zone(color: Color, king: u8) -> u64 {
+    let around = KING_MOVES[king as usize] | 1u64 << king;
+    around | if color.is_white() { around << 8 } else { around >> 8 }
+}
+
+/// The `n` ranks directly in front of `king` from `color`'s side.
+fn ranks_ahead(color: Color, king: u8, n: u8) -> u64 {
+    let rank = (king / 8) as i32;
+    (1..=n as i32)
+        .map(|i| if color.is_white() { rank + i } else { rank - i })
+        .filter(|r| (0..8).contains(r))
+        .fold(0, |bb, r| bb | 0xFFu64 << (r * 8))
+}
@@ -0,0 +1,97 @@
+use crate::attacks::sliding::{diagonal_attacks, orthogonal_attacks};
+use crate::color::Color;
+use crate::eval::{Tapered, Terms, BISHOP_PAIR_INDEX, MOBILITY_INDEX, OUTPOST_INDEX, ROOK_FILE_INDEX, ROOK_SEVENTH_INDEX, TRAPPED_INDEX};
+use crate::engines::evaluate::pawns::{adjacent_files, file_bb, forward_ranks, pawn_attacks, relative_rank};
+use crate::piece::Piece;
+use crate::position::Position;
+use crate::tables::{KNIGHT_MOVES, PAWN_ATTACKS};
+
+/// Safe squares a piece typically has; fewer costs, more earns.
+const MOBILITY_BASE: [i32; 4] = [4, 6, 7, 13];
+
+
+/// Mobility and piece activity (White's point of view) as separate middlegame and endgame
+/// scores: safe squares per piece, the bishop pair, rooks on open files and the seventh
+/// rank, knight outposts and trapped bishops and rooks.
+pub(crate) fn evaluate(pos: &Position) -> (i32, i32) {
+    let mut score = Tapered::default();
+    terms(pos, &mut score);
+    (score.mg, score.eg)
+}
+
+/// The terms one by one, as the tuner counts them.
+pub(crate) fn terms(pos: &Position, terms: &mut impl Terms) {
+    activity(pos, Color::White, terms);
+    activity(pos, Color::Black, terms);
+}
+
+fn activity(pos: &Position, color: Color, terms: &mut impl Terms) {
+    let sign = if color.is_white() { 1 } else { -1 };
+    let occupied = pos.occupied();
+    let ours   = pos.pawns(color);
+    let theirs = pos.pawns(!color);
+    // squares neither blocked by our own pieces nor covered by an enemy pawn
+    let safe = !pos.occupancy(color) & !pawn_attacks(theirs, !color);
+
+    for (i, piece) in [Piece::Knight, Piece::Bishop, Piece::Rook, Piece::Queen].into_iter().enumerate() {
+        let (squares, count) = pos.piece_list(piece, color);
+        for &sq in &squares[..count] {
+            let attacks = match piece {
+                Piece::Knight => KNIGHT_MOVES[sq as usize],
+                Piece::Bishop => diagonal_attacks(sq as usize, occupied),
+                Piece::Rook   => orthogonal_attacks(sq as usize, occupied),
+                _             => diagonal_attacks(sq as usize, occupied) | orthogonal_attacks(sq as usize, occupied),
+            };
+            let mobility = (attacks & safe).count_ones() as i32;
+            terms.add(MOBILITY_INDEX + i, sign * (mobility - MOBILITY_BASE[i]));
+
+            match piece {
+                Piece::Knight if outpost(color, sq, ours, theirs) => terms.add(OUTPOST_INDEX, sign),
+                Piece::Bishop if trapped_bishop(color, sq, theirs) => terms.add(TRAPPED_INDEX, -sign),
+                Piece::Rook => {
+                    let file = file_bb(sq);
+                    if ours & file == 0 {
+                        terms.add(ROOK_FILE_INDEX + if theirs & file == 0 { 0 } else { 1 }, sign);
+                    }
+                    if relative_rank(color, sq) == 6 {
+                        terms.add(ROOK_SEVENTH_INDEX, sign);
+                    }
+                    if trapped_rook(pos, color, sq, mobility) {
+                        terms.add(TRAPPED_INDEX + 1, -sign);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if pos.piece_count(Piece::Bishop, color) >= 2 {
+        terms.add(BISHOP_PAIR_INDEX, sign);
+    }
+}
+
+/// A knight on the 4th to 6th rank, defended by a pawn, that no enemy pawn can chase away.
+fn outpost(color: Color, sq: u8, ours: u64, theirs: u64) -> bool {
+    let defended = ours & PAWN_ATTACKS[!color as usize][sq as usize] != 0;
+    let attackable = theirs & forward_ranks(color, sq) & adjacent_files(sq) != 0;
+    (3..=5).contains(&relative_rank(color, sq)) && defended && !attackable
+}
+
+/// The classic bishop trap: Bxa7 answered by ...b6 (or Bxh7 by ...g6).
+fn trapped_bishop(color: Color, sq: u8, theirs: u64) -> bool {
+    let pawn = match (relative_rank(color, sq), sq % 8) {
+        (6, 0) => sq as i32 + if color.is_white() { -7 } else { 9 },
+        (6, 7) => sq as i32 + if color.is_white() { -9 } else { 7 },
+        _      => return false,
+    };
+    theirs & (1u64 << pawn) != 0
+}
+
+/// A rook with hardly any moves on the back rank, cut off from the centre by its own king
+/// (e.g. Kf1 with the rook still on h1).
+fn trapped_rook(pos: &Position, color: Color, sq: u8, mobility: i32) -> bool {
+    let king = pos.king_square(color);
+    let same_side = (king % 8 < 4) == (sq % 8 < king % 8);
+    let back_rank = relative_rank(color, sq) == 0 && relative_rank(color, king) == 0;
+    mobility <= 3 && back_rank && same_side && king % 8 != 3 && king % 8 != 4
+}
@@ -1,3 +1,10 @@
 pub(crate) mod e1;
 pub(crate) mod e2;
-pub(crate) mod e3;
\ No newline at end of file
+pub(crate) mod e3;
+pub(crate) mod e4;
+pub(crate) mod e5;
+pub(crate) mod e6;
+pub(crate) mod e7;
+pub(crate) mod pawns;
+pub(crate) mod king_safety;
+pub(crate) mod mobility;
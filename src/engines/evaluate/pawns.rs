@@ -0,0 +1,173 @@
+use std::cell::RefCell;
+use crate::bitboards::{FILE_A, FILE_H};
+use crate::color::Color;
+use crate::eval::{Tapered, Terms, BACKWARD_INDEX, CONNECTED_INDEX, DOUBLED_INDEX, FREE_PATH_INDEX, ISOLATED_INDEX, KING_PROXIMITY_INDEX, PASSED_INDEX};
+use crate::position::Position;
+use crate::tables::PAWN_ATTACKS;
+
+/// Entries in each thread's pawn hash; pawn structures repeat so often that a small table
+/// answers nearly every probe.
+const PAWN_TABLE_SIZE: usize = 1 << 14;
+
+/// How the kings' distances to a passed pawn's stop square add up: the enemy king's counts
+/// this many times, ours against it. The tuned weights scale the sum by rank.
+const ENEMY_KING_DISTANCE: i32 = 5;
+const OWN_KING_DISTANCE:   i32 = 2;
+
+
+/// What the pawn hash keeps: everything that depends on the pawns alone.
+#[derive(Copy, Clone, Default)]
+struct PawnEntry {
+    key:    u64,
+    mg:     i32,        // White - Black
+    eg:     i32,
+    passed: [u64; 2],   // passed pawns per colour, for the terms that also need the kings
+}
+
+thread_local! {
+    // One table per search thread, so helpers never contend for it. A position without
+    // pawns has key 0 and matches the empty entries, which are correct for it.
+    static PAWN_TABLE: RefCell<Vec<PawnEntry>> = RefCell::new(vec![PawnEntry::default(); PAWN_TABLE_SIZE]);
+}
+
+/// Pawn structure (White's point of view) as separate middlegame and endgame scores:
+/// doubled, isolated, backward, connected and passed pawns, plus the passed pawns'
+/// free path and distance to both kings.
+pub(crate) fn evaluate(pos: &Position) -> (i32, i32) {
+    let entry = PAWN_TABLE.with(|table| {
+        let mut table = table.borrow_mut();
+        let slot = &mut table[pos.pawn_key() as usize % PAWN_TABLE_SIZE];
+        if slot.key != pos.pawn_key() {
+            let mut score = Tapered::default();
+            let passed = structure(pos, &mut score);
+            *slot = PawnEntry { key: pos.pawn_key(), mg: score.mg, eg: score.eg, passed };
+        }
+        *slot
+    });
+
+    let mut score = Tapered { mg: entry.mg, eg: entry.eg };
+    passed_pawns(pos, entry.passed, &mut score);
+    (score.mg, score.eg)
+}
+
+/// The same terms for the tuner, without the pawn hash.
+pub(crate) fn terms(pos: &Position, terms: &mut impl Terms) {
+    let passed = structure(pos, terms);
+    passed_pawns(pos, passed, terms);
+}
+
+/// The terms that depend on the pawns alone; returns the passed pawns per colour.
+fn structure(pos: &Position, terms: &mut impl Terms) -> [u64; 2] {
+    let mut passed = [0; 2];
+    for color in [Color::White, Color::Black] {
+        let sign = if color.is_white() { 1 } else { -1 };
+        let ours   = pos.pawns(color);
+        let theirs = pos.pawns(!color);
+        let their_attacks = pawn_attacks(theirs, !color);
+
+        let mut pawns = ours;
+        while pawns != 0 {
+            let sq = pawns.trailing_zeros() as u8;
+            pawns &= pawns - 1;
+            let rank = relative_rank(color, sq);
+            let ahead = forward_ranks(color, sq);
+
+            let isolated  = ours & adjacent_files(sq) == 0;
+            let supported = ours & PAWN_ATTACKS[!color as usize][sq as usize] != 0;
+            let phalanx   = ours & adjacent_files(sq) & rank_bb(sq) != 0;
+            // no friendly pawn left that could ever defend it, and it cannot advance safely
+            let backward  = !isolated
+                && ours & adjacent_files(sq) & !ahead == 0
+                && their_attacks & stop_square(color, sq) != 0;
+
+            if ours & ahead & file_bb(sq) != 0 {
+                // the rear pawn of a doubled pair; only the front one can become passed
+                terms.add(DOUBLED_INDEX, -sign);
+            } else if theirs & ahead & (file_bb(sq) | adjacent_files(sq)) == 0 {
+                terms.add(PASSED_INDEX + rank, sign);
+                passed[color as usize] |= 1 << sq;
+            }
+
+            if isolated {
+                terms.add(ISOLATED_INDEX, -sign);
+            } else if backward {
+                terms.add(BACKWARD_INDEX, -sign);
+            }
+
+            if supported || phalanx {
+                terms.add(CONNECTED_INDEX + rank, sign * if phalanx { 2 } else { 1 });
+            }
+        }
+    }
+    passed
+}
+
+/// Terms of the passed pawns that also depend on the other pieces: a free path to the last
+/// rank, and the enemy king being far from (and our king near to) the stop square.
+fn passed_pawns(pos: &Position, passed: [u64; 2], terms: &mut impl Terms) {
+    let distance = |a: u8, b: u8| (a % 8).abs_diff(b % 8).max((a / 8).abs_diff(b / 8)) as i32;
+    for color in [Color::White, Color::Black] {
+        let sign = if color.is_white() { 1 } else { -1 };
+        let mut pawns = passed[color as usize];
+        while pawns != 0 {
+            let sq = pawns.trailing_zeros() as u8;
+            pawns &= pawns - 1;
+            let rank = relative_rank(color, sq);
+            let stop = stop_square(color, sq).trailing_zeros() as u8;
+
+            let kings = ENEMY_KING_DISTANCE * distance(pos.king_square(!color), stop)
+                - OWN_KING_DISTANCE * distance(pos.king_square(color), stop);
+            terms.add(KING_PROXIMITY_INDEX + rank, sign * kings);
+            if pos.occupied() & forward_ranks(color, sq) & file_bb(sq) == 0 {
+                terms.add(FREE_PATH_INDEX + rank, sign);
+            }
+        }
+    }
+}
+
+
+#[inline(always)]
+pub(super) fn file_bb(sq: u8) -> u64 {
+    FILE_A << (sq % 8)
+}
+
+#[inline(always)]
+fn rank_bb(sq: u8) -> u64 {
+    0xFF << (sq / 8 * 8)
+}
+
+#[inline(always)]
+pub(super) fn adjacent_files(sq: u8) -> u64 {
+    let file = file_bb(sq);
+    ((file << 1) & !FILE_A) | ((file >> 1) & !FILE_H)
+}
+
+/// Every rank in front of `sq` from `color`'s side.
+#[inline(always)]
+pub(super) fn forward_ranks(color: Color, sq: u8) -> u64 {
+    let rank = sq / 8;
+    if color.is_white() {
+        if rank == 7 { 0 } else { !0u64 << ((rank + 1) * 8) }
+    } else {
+        (1u64 << (rank * 8)) - 1
+    }
+}
+
+#[inline(always)]
+fn stop_square(color: Color, sq: u8) -> u64 {
+    if color.is_white() { 1u64 << (sq + 8) } else { 1u64 << (sq - 8) }
+}
+
+#[inline(always)]
+pub(super) fn relative_rank(color: Color, sq: u8) -> usize {
+    if color.is_white() { (sq / 8) as usize } else { 7 - (sq / 8) as usize }
+}
+
+#[inline(always)]
+pub(super) fn pawn_attacks(pawns: u64, color: Color) -> u64 {
+    if color.is_white() {
+        ((pawns << 7) & !FILE_H) | ((pawns << 9) & !FILE_A)
+    } else {
+        ((pawns >> 9) & !FILE_H) | ((pawns >> 7) & !FILE_A)
+    }
+}
@@ -2,8 +2,15 @@ pub mod engine_manager;
 
 mod search;
 mod transposition_table;
-mod evaluate;
+pub(crate) mod evaluate;
 mod constants;
 mod history;
 mod stats;
 mod pv;
+pub mod score;
+pub mod time_manager;
+pub mod book;
+pub mod tablebase;
+pub mod bitbase;
+pub mod nnue;
+pub mod search_params;
@@ -0,0 +1,230 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use crate::color::Color;
+use crate::piece::Piece;
+use crate::position::Position;
+
+/// Piece-square inputs of one perspective: 2 colours x 6 pieces x 64 squares.
+pub const INPUTS: usize = 768;
+/// Neurons per perspective in the hidden layer.
+pub const HIDDEN: usize = 256;
+
+// quantisation of the trained float weights, as in bullet's simple perspective nets
+const QA: i32 = 255;   // hidden layer
+const QB: i32 = 64;    // output layer
+const SCALE: i32 = 400;
+
+/// Size of a weights file: i16 feature weights [INPUTS][HIDDEN], feature biases [HIDDEN],
+/// output weights [2 * HIDDEN] (side to move first) and the output bias, little-endian.
+/// Trainers may pad the file; anything after this is ignored.
+pub const FILE_SIZE: usize = 2 * (INPUTS * HIDDEN + HIDDEN + 2 * HIDDEN + 1);
+
+
+/// A 768→HIDDEN→1 network seen from both sides (a "perspective" net): each side has its own
+/// accumulator over the same weights, the side to move's half goes first into the output.
+pub struct Network {
+    feature_weights: Vec<[i16; HIDDEN]>,  // one column per input
+    feature_bias:    [i16; HIDDEN],
+    output_weights:  [i16; 2 * HIDDEN],
+    output_bias:     i16,
+}
+
+impl fmt::Debug for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Network({INPUTS}->{HIDDEN}->1)")
+    }
+}
+
+impl Network {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Network> {
+        Network::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Network> {
+        if bytes.len() < FILE_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected a {INPUTS}->{HIDDEN}->1 network of {FILE_SIZE} bytes, got {}", bytes.len()),
+            ));
+        }
+        let mut values = bytes.chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]]));
+        let mut next = || values.next().expect("length checked above");
+
+        let mut feature_weights = vec![[0i16; HIDDEN]; INPUTS];
+        for column in feature_weights.iter_mut() {
+            column.iter_mut().for_each(|w| *w = next());
+        }
+        let mut feature_bias = [0i16; HIDDEN];
+        feature_bias.iter_mut().for_each(|b| *b = next());
+        let mut output_weights = [0i16; 2 * HIDDEN];
+        output_weights.iter_mut().for_each(|w| *w = next());
+        let output_bias = next();
+
+        Ok(Network { feature_weights, feature_bias, output_weights, output_bias })
+    }
+
+    /// Score for the side to move, in centipawns.
+    fn output(&self, ours: &[i16; HIDDEN], theirs: &[i16; HIDDEN]) -> i32 {
+        let (us, them) = self.output_weights.split_at(HIDDEN);
+        let sum = simd::crelu_dot(ours, us) + simd::crelu_dot(theirs, them) + self.output_bias as i32;
+        sum * SCALE / (QA * QB)
+    }
+}
+
+
+/// The hidden layer of both perspectives for the current position, kept up to date by
+/// `Position` as pieces are added, removed and moved.
+#[derive(Clone, Debug)]
+pub struct Accumulator {
+    network: Arc<Network>,
+    values:  [[i16; HIDDEN]; 2],  // [perspective]
+}
+
+impl Accumulator {
+    /// Builds the accumulators from scratch.
+    pub(crate) fn new(network: Arc<Network>, pos: &Position) -> Accumulator {
+        let mut acc = Accumulator { values: [network.feature_bias; 2], network };
+        for piece in 0..6 {
+            for color in [Color::White, Color::Black] {
+                let (squares, count) = pos.piece_list(Piece::from(piece), color);
+                for &sq in &squares[..count] {
+                    acc.add(Piece::from(piece), color, sq as usize);
+                }
+            }
+        }
+        acc
+    }
+
+    pub(crate) fn network(&self) -> &Arc<Network> {
+        &self.network
+    }
+
+    #[inline(always)]
+    pub(crate) fn add(&mut self, piece: Piece, color: Color, sq: usize) {
+        for perspective in [Color::White, Color::Black] {
+            let column = &self.network.feature_weights[feature(perspective, piece, color, sq)];
+            simd::add_assign(&mut self.values[perspective as usize], column);
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn remove(&mut self, piece: Piece, color: Color, sq: usize) {
+        for perspective in [Color::White, Color::Black] {
+            let column = &self.network.feature_weights[feature(perspective, piece, color, sq)];
+            simd::sub_assign(&mut self.values[perspective as usize], column);
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn move_piece(&mut self, piece: Piece, color: Color, from: usize, to: usize) {
+        self.remove(piece, color, from);
+        self.add(piece, color, to);
+    }
+
+    /// Evaluation from White's point of view.
+    pub(crate) fn evaluate(&self, side_to_move: Color) -> i16 {
+        let us = side_to_move as usize;
+        let score = self.network.output(&self.values[us], &self.values[1 - us]);
+        let score = score.clamp(-(i16::MAX as i32) / 2, i16::MAX as i32 / 2) as i16;
+        if side_to_move.is_white() { score } else { -score }
+    }
+}
+
+/// Input index of a piece as seen from `perspective`: its own pieces first, board flipped
+/// for Black so that both sides see themselves playing up the board.
+#[inline(always)]
+fn feature(perspective: Color, piece: Piece, color: Color, sq: usize) -> usize {
+    let theirs = (color as usize != perspective as usize) as usize;
+    let sq = if perspective.is_white() { sq } else { sq ^ 56 };
+    theirs * 384 + piece as usize * 64 + sq
+}
+
+
+/// The vector kernels, with AVX2 picked at run time and plain loops everywhere else.
+mod simd {
+    use super::{HIDDEN, QA};
+
+    pub(super) fn add_assign(acc: &mut [i16; HIDDEN], column: &[i16; HIDDEN]) {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is available
+            return unsafe { avx2::add_assign(acc, column) };
+        }
+        acc.iter_mut().zip(column).for_each(|(a, w)| *a = a.wrapping_add(*w));
+    }
+
+    pub(super) fn sub_assign(acc: &mut [i16; HIDDEN], column: &[i16; HIDDEN]) {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is available
+            return unsafe { avx2::sub_assign(acc, column) };
+        }
+        acc.iter_mut().zip(column).for_each(|(a, w)| *a = a.wrapping_sub(*w));
+    }
+
+    /// Sum of clamp(acc, 0, QA) * weight over the hidden layer.
+    pub(super) fn crelu_dot(acc: &[i16; HIDDEN], weights: &[i16]) -> i32 {
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is available
+            return unsafe { avx2::crelu_dot(acc, weights) };
+        }
+        crelu_dot_scalar(acc, weights)
+    }
+
+    fn crelu_dot_scalar(acc: &[i16; HIDDEN], weights: &[i16]) -> i32 {
+        acc.iter().zip(weights).map(|(&a, &w)| (a as i32).clamp(0, QA) * w as i32).sum()
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    mod avx2 {
+        use std::arch::x86_64::*;
+        use super::super::{HIDDEN, QA};
+
+        const LANES: usize = 16;  // i16 per 256-bit register
+
+        #[target_feature(enable = "avx2")]
+        pub(in super::super) unsafe fn add_assign(acc: &mut [i16; HIDDEN], column: &[i16; HIDDEN]) {
+            for i in (0..HIDDEN).step_by(LANES) {
+                unsafe {
+                    let a = _mm256_loadu_si256(acc.as_ptr().add(i) as *const __m256i);
+                    let w = _mm256_loadu_si256(column.as_ptr().add(i) as *const __m256i);
+                    _mm256_storeu_si256(acc.as_mut_ptr().add(i) as *mut __m256i, _mm256_add_epi16(a, w));
+                }
+            }
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub(in super::super) unsafe fn sub_assign(acc: &mut [i16; HIDDEN], column: &[i16; HIDDEN]) {
+            for i in (0..HIDDEN).step_by(LANES) {
+                unsafe {
+                    let a = _mm256_loadu_si256(acc.as_ptr().add(i) as *const __m256i);
+                    let w = _mm256_loadu_si256(column.as_ptr().add(i) as *const __m256i);
+                    _mm256_storeu_si256(acc.as_mut_ptr().add(i) as *mut __m256i, _mm256_sub_epi16(a, w));
+                }
+            }
+        }
+
+        #[target_feature(enable = "avx2")]
+        pub(in super::super) unsafe fn crelu_dot(acc: &[i16; HIDDEN], weights: &[i16]) -> i32 {
+            assert!(weights.len() >= HIDDEN);
+            let zero = _mm256_setzero_si256();
+            let max = _mm256_set1_epi16(QA as i16);
+            let mut sum = _mm256_setzero_si256();
+            for i in (0..HIDDEN).step_by(LANES) {
+                unsafe {
+                    let a = _mm256_loadu_si256(acc.as_ptr().add(i) as *const __m256i);
+                    let w = _mm256_loadu_si256(weights.as_ptr().add(i) as *const __m256i);
+                    let clipped = _mm256_min_epi16(_mm256_max_epi16(a, zero), max);
+                    // pairwise i16 x i16 -> i32, exact since |clipped * w| < 2^23
+                    sum = _mm256_add_epi32(sum, _mm256_madd_epi16(clipped, w));
+                }
+            }
+            let mut lanes = [0i32; 8];
+            unsafe { _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, sum) };
+            lanes.iter().sum()
+        }
+    }
+}
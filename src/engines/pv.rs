@@ -47,6 +47,15 @@ impl PV {
         }
     }
 
+    /// The principal variation collected at the root, up to the first null move.
+    pub fn line(&self) -> Vec<Move> {
+        self.pv_array[..MAX_DEPTH as usize]
+            .iter()
+            .copied()
+            .take_while(|mv| !mv.is_null())
+            .collect()
+    }
+
     #[inline(always)]
     fn row_start(&self, ply: u16) -> usize {
         (ply * MAX_DEPTH - (ply * (ply).saturating_sub(1) / 2)) as usize
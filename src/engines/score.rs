@@ -0,0 +1,97 @@
+use std::fmt;
+use std::ops::Neg;
+
+/// Raw search score of "mate right now". Mate scores are stored relative to the root:
+/// `MATE - ply` for giving mate `ply` half-moves from the root, `-MATE + ply` for being mated.
+pub const MATE: i16 = 32_000;
+/// Anything at least this far from zero is a mate score.
+pub const MATE_BOUND: i16 = MATE - 256;
+/// Raw score of a tablebase win at the root; see [`crate::engines::tablebase::wdl_score`].
+pub const TB_WIN: i16 = 20_000;
+
+#[inline(always)]
+pub const fn mate_in(ply: u16) -> i16 {
+    MATE - ply as i16
+}
+
+#[inline(always)]
+pub const fn mated_in(ply: u16) -> i16 {
+    -MATE + ply as i16
+}
+
+#[inline(always)]
+pub const fn is_mate(score: i16) -> bool {
+    score >= MATE_BOUND || score <= -MATE_BOUND
+}
+
+/// Converts a root-relative mate score into one relative to the node at `ply`, so that a
+/// transposition-table entry means the same thing wherever it is probed from.
+#[inline(always)]
+pub const fn to_tt(score: i16, ply: u16) -> i16 {
+    if score >= MATE_BOUND {
+        score + ply as i16
+    } else if score <= -MATE_BOUND {
+        score - ply as i16
+    } else {
+        score
+    }
+}
+
+/// Inverse of [`to_tt`] for an entry probed at `ply`.
+#[inline(always)]
+pub const fn from_tt(score: i16, ply: u16) -> i16 {
+    if score >= MATE_BOUND {
+        score - ply as i16
+    } else if score <= -MATE_BOUND {
+        score + ply as i16
+    } else {
+        score
+    }
+}
+
+
+/// A search result as shown to the user: centipawns, or moves until mate
+/// (negative when the side to move is getting mated).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Score {
+    Cp(i16),
+    Mate(i16),
+}
+
+impl Score {
+    pub fn from_search(raw: i16) -> Score {
+        if raw >= MATE_BOUND {
+            Score::Mate((MATE - raw + 1) / 2)
+        } else if raw <= -MATE_BOUND {
+            Score::Mate(-((MATE + raw + 1) / 2))
+        } else {
+            Score::Cp(raw)
+        }
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Score::Cp(0)
+    }
+}
+
+impl Neg for Score {
+    type Output = Score;
+
+    fn neg(self) -> Score {
+        match self {
+            Score::Cp(cp)   => Score::Cp(-cp),
+            Score::Mate(n)  => Score::Mate(-n),
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Cp(cp)  => write!(f, "{:+.2}", *cp as f32 / 100.0),
+            Score::Mate(n) => write!(f, "#{n}"),
+        }
+    }
+}
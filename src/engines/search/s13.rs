@@ -1,6 +1,7 @@
 use crate::attacks::movegen::all_moves;
 use crate::engines::constants::MAX_DEPTH;
 use crate::engines::engine_manager::Ctx;
+use crate::engines::score::mated_in;
 use crate::engines::transposition_table::Bound;
 use crate::mov::{MAX_MOVES, Move, MoveList};
 use crate::position::{Position, Status};
@@ -111,18 +112,19 @@ pub(crate) fn quiescence(
     mut alpha: i16,
     beta: i16,
     color: i16,
-    deadline: Instant,
+    deadline: Option<Instant>,
     ctx: &mut Ctx,
 ) -> Option<(i16, Move)> {
+    ctx.nodes += 1;
     /* ---- 0.  time check --------------------------------------- */
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None;
     }
 
     let mut qhash = Move::null();
 
     /* ---- 1.  TT probe ----------------------------------------- */
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         // depth == 0 entries are quiescence results
         qhash = e.mv;
         match e.bound {
@@ -144,6 +146,7 @@ pub(crate) fn quiescence(
             stand_pat,
             Move::null(),
             ctx.generation,
+            ctx.ply,
         );
         return Some((beta, Move::null())); // fail-high
     }
@@ -167,6 +170,7 @@ pub(crate) fn quiescence(
             stand_pat,
             Move::null(),
             ctx.generation,
+            ctx.ply,
         );
         return Some((stand_pat, Move::null()));
     }
@@ -192,7 +196,7 @@ pub(crate) fn quiescence(
         };
 
         if score >= beta {
-            ctx.tt.store(pos.zobrist(), 0, Bound::Lower, score, m, ctx.generation);
+            ctx.tt.store(pos.zobrist(), 0, Bound::Lower, score, m, ctx.generation, ctx.ply);
             return Some((beta, m)); // fail-high
         }
         if score > alpha {
@@ -208,12 +212,11 @@ pub(crate) fn quiescence(
         Bound::Upper // searched moves but didn’t reach beta
     };
     ctx.tt
-        .store(pos.zobrist(), 0, bound, alpha, best_move, ctx.generation);
+        .store(pos.zobrist(), 0, bound, alpha, best_move, ctx.generation, ctx.ply);
 
     Some((alpha, best_move))
 }
 
-const MATE_SCORE: i16 = 10_000; // any value ≫ evaluate range
 
 pub(crate) fn negamax(
     pos: &mut Position,
@@ -221,12 +224,12 @@ pub(crate) fn negamax(
     mut alpha: i16,
     beta: i16,
     color: i16,
-    deadline: Instant,
+    deadline: Option<Instant>,
     ctx: &mut Ctx,
 ) -> Option<(i16, Move)> {
     ctx.nodes += 1;
     /* ----- 0. abort if out of time -------------------------------- */
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None; // bubble up timeout
     }
 
@@ -274,7 +277,7 @@ pub(crate) fn negamax(
     /* ----- 1. TT probe ------------------------------------------- */
     let mut hash_move = Move::null();
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         hash_move = e.mv;
         let entry_ok = e.depth >= depth;
 
@@ -298,7 +301,7 @@ pub(crate) fn negamax(
     let mut moves = all_moves(pos);
     if moves.is_empty() {
         let s = match pos.get_game_result() {
-            Status::Checkmate(_) => -color * (MATE_SCORE + depth as i16),
+            Status::Checkmate(_) => color * mated_in(ctx.ply),
             _ => 0,
         };
         return Some((color * s, Move::null()));
@@ -430,6 +433,7 @@ pub(crate) fn negamax(
         returned,
         best_move,
         ctx.generation,
+        ctx.ply,
     );
 
     Some((returned, best_move))
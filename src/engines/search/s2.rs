@@ -1,16 +1,16 @@
 use std::time::{Instant};
 use crate::attacks::movegen::all_moves;
 use crate::engines::engine_manager::Ctx;
+use crate::engines::score::mated_in;
 use crate::mov::Move;
 use crate::position::{Position, Status};
 
-const MATE_SCORE: i16 = 10_000;
 
-pub(crate) fn negamax(pos: &mut Position, depth: u8, a: i16, b: i16, color: i16, deadline: Instant, ctx: &mut Ctx) -> Option<(i16, Move)> {
+pub(crate) fn negamax(pos: &mut Position, depth: u8, a: i16, b: i16, color: i16, deadline: Option<Instant>, ctx: &mut Ctx) -> Option<(i16, Move)> {
     ctx.nodes += 1;
 
 
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None;
     }
     if depth == 0 {
@@ -20,7 +20,7 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, a: i16, b: i16, color: i16,
     let moves = all_moves(pos);
     if moves.is_empty() {
         let s = match pos.get_game_result() {
-            Status::Checkmate(_) => -color * (MATE_SCORE + depth as i16),
+            Status::Checkmate(_) => color * mated_in(ctx.ply),
             _                    => 0,
         };
         return Some((color * s, Move::null()));
@@ -31,9 +31,11 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, a: i16, b: i16, color: i16,
 
     for m in moves.iter() {
         pos.do_move(m);
+        ctx.ply += 1;
 
         // recurse from opponent’s view
         let reply = negamax(pos, depth - 1, a, b, -color, deadline, ctx);
+        ctx.ply -= 1;
         pos.undo_move();
 
         match reply {
@@ -1,19 +1,19 @@
 use std::time::{Instant};
 use crate::attacks::movegen::all_moves;
 use crate::engines::engine_manager::Ctx;
+use crate::engines::score::mated_in;
 use crate::mov::Move;
 use crate::position::{Position, Status};
 
-const MATE_SCORE: i16 = 10_000;
 
 /// α‑β NegaMax that aborts cleanly when `deadline` is hit.
 ///
 /// Returns **`None`** instead of a bogus score when time is up.       ★
-pub(crate) fn negamax(pos: &mut Position, depth: u8, mut a: i16, b: i16, color: i16, deadline: Instant, ctx: &mut Ctx) -> Option<(i16, Move)> {
+pub(crate) fn negamax(pos: &mut Position, depth: u8, mut a: i16, b: i16, color: i16, deadline: Option<Instant>, ctx: &mut Ctx) -> Option<(i16, Move)> {
 
     ctx.nodes += 1;
 
-    if Instant::now() >= deadline { return None; }
+    if ctx.should_stop(deadline) { return None; }
 
     // base‑case: leaf
     if depth == 0 {
@@ -23,7 +23,7 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, mut a: i16, b: i16, color:
     let moves = all_moves(pos);
     if moves.is_empty() {
         let s = match pos.get_game_result() {
-            Status::Checkmate(_) => -color * (MATE_SCORE + depth as i16),
+            Status::Checkmate(_) => color * mated_in(ctx.ply),
             _                    => 0,
         };
         return Some((color * s, Move::null()));
@@ -33,7 +33,9 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, mut a: i16, b: i16, color:
 
     for m in moves.iter() {
         pos.do_move(m);
+        ctx.ply += 1;
         let reply = negamax(pos, depth - 1, -b, -a, -color, deadline, ctx);
+        ctx.ply -= 1;
         pos.undo_move();
 
         match reply {                                                  // ★
@@ -1,6 +1,7 @@
 use std::time::{Instant};
 use crate::attacks::movegen::all_moves;
 use crate::engines::engine_manager::Ctx;
+use crate::engines::score::{mated_in, MATE};
 use crate::engines::transposition_table::{Bound};
 use crate::mov::Move;
 use crate::position::{Position, Status};
@@ -9,7 +10,6 @@ use crate::position::{Position, Status};
 
 
 
-const MATE_SCORE: i16 = 10_000;   // any value ≫ evaluate range
 
 
 /// alpha‑beta Negamax **with TT** that fits the new `SearchFn` signature.
@@ -17,10 +17,10 @@ const MATE_SCORE: i16 = 10_000;   // any value ≫ evaluate range
 /// • returns `None` as soon as the deadline is hit
 /// • uses `ctx.tt` for probing / storing
 /// • uses `ctx.eval_fn` for the static evaluation
-pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16, color: i16, deadline: Instant, ctx: &mut Ctx,) -> Option<(i16, Move)> {
+pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16, color: i16, deadline: Option<Instant>, ctx: &mut Ctx,) -> Option<(i16, Move)> {
 
     /* ----- 0. abort if out of time ------------------------------------ */
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None;                     // bubble up timeout
     }
 
@@ -33,7 +33,7 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16,
     let mut entry_ok  = false;          // is depth high enough for pruning?
     let mut entry     = None;
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         entry_ok   = e.depth >= depth;  // evaluate later
         entry      = Some(e);
     }
@@ -62,20 +62,22 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16,
     let moves = all_moves(pos);
     if moves.is_empty() {
         let s = match pos.get_game_result() {
-            Status::Checkmate(_) => -color * (MATE_SCORE + depth as i16),
+            Status::Checkmate(_) => color * mated_in(ctx.ply),
             _                    => 0,
         };
         return Some((color * s, Move::null()));
     }
 
     /* ----- 4. search loop --------------------------------------------- */
-    let mut best      = -MATE_SCORE;
+    let mut best      = -MATE;
     let mut best_move = Move::null();
 
     for m in moves.iter() {
         pos.do_move(m);
+        ctx.ply += 1;
         let child = negamax(pos, depth - 1, -beta, -alpha,
                             -color, deadline, ctx);
+        ctx.ply -= 1;
         pos.undo_move();
 
         // propagate timeout as‑is
@@ -98,7 +100,7 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16,
     /* ----- 5. store in TT --------------------------------------------- */
     let bound = if best <= orig_alpha { Bound::Upper } else if best >= beta { Bound::Lower } else { Bound::Exact };
 
-    ctx.tt.store(pos.zobrist(), depth, bound, alpha, best_move, ctx.generation);
+    ctx.tt.store(pos.zobrist(), depth, bound, alpha, best_move, ctx.generation, ctx.ply);
 
 
     Some((best, best_move))
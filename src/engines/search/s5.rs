@@ -2,26 +2,21 @@ use std::time::{Instant};
 use crate::attacks::movegen::all_moves;
 use crate::engines::constants::MAX_DEPTH;
 use crate::engines::engine_manager::Ctx;
+use crate::engines::score::mated_in;
 use crate::engines::transposition_table::{Bound};
 use crate::mov::Move;
 use crate::position::{Position, Status};
 
-/// Pure material values, index = `Piece as usize`
-
-
-
-const MATE_SCORE: i16 = 10_000;   // any value ≫ evaluate range
-
 
 /// alpha‑beta Negamax **with TT** that fits the new `SearchFn` signature.
 ///
 /// • returns `None` as soon as the deadline is hit
 /// • uses `ctx.tt` for probing / storing
 /// • uses `ctx.eval_fn` for the static evaluation
-pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16, color: i16, deadline: Instant, ctx: &mut Ctx,) -> Option<(i16, Move)> {
+pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16, color: i16, deadline: Option<Instant>, ctx: &mut Ctx,) -> Option<(i16, Move)> {
 
     /* ----- 0. abort if out of time ------------------------------------ */
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None;                     // bubble up timeout
     }
 
@@ -33,7 +28,7 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16,
     let mut entry_ok  = false;          // is depth high enough for pruning?
     let mut entry     = None;
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         entry_ok   = e.depth >= depth;  // evaluate later
         entry      = Some(e);
     }
@@ -62,7 +57,7 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16,
     let mut moves = all_moves(pos);
     if moves.is_empty() {
         let s = match pos.get_game_result() {
-            Status::Checkmate(_) => -color * (MATE_SCORE + depth as i16),
+            Status::Checkmate(_) => color * mated_in(ctx.ply),
             _                    => 0,
         };
         return Some((color * s, Move::null()));
@@ -145,7 +140,7 @@ pub(crate) fn negamax(pos: &mut Position, depth: u8, mut alpha: i16, beta: i16,
                  bound,
                  alpha,
                  ctx.pv_array[ctx.pv_index],
-                 ctx.generation);
+                 ctx.generation, ctx.ply);
 
 
 
@@ -2,12 +2,12 @@ use std::time::Instant;
 use crate::attacks::movegen::all_moves;
 use crate::engines::constants::MAX_DEPTH;
 use crate::engines::engine_manager::Ctx;
+use crate::engines::score::mated_in;
 use crate::engines::transposition_table::Bound;
 use crate::mov::Move;
 use crate::position::{Position, Status};
 
 /// Mate score base value (distance-to-mate is added/subtracted on top of it)
-const MATE_SCORE: i16 = 10_000; // any value ≫ evaluate range
 
 /// Alpha–beta Negamax **with Transposition Table**
 ///
@@ -20,11 +20,11 @@ pub(crate) fn negamax(
     mut alpha: i16,
     beta: i16,
     color: i16,
-    deadline: Instant,
+    deadline: Option<Instant>,
     ctx: &mut Ctx,
 ) -> Option<(i16, Move)> {
     /* ----- 0. abort if out of time -------------------------------- */
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None; // bubble up timeout
     }
 
@@ -35,7 +35,7 @@ pub(crate) fn negamax(
     /* ----- 1. TT probe ------------------------------------------- */
     let mut hash_move = Move::null();
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         hash_move = e.mv;
         let entry_ok = e.depth >= depth;
 
@@ -60,6 +60,7 @@ pub(crate) fn negamax(
             sc,
             Move::null(),
             ctx.generation,
+            ctx.ply,
         );
         return Some((sc, Move::null()));
     }
@@ -68,7 +69,7 @@ pub(crate) fn negamax(
     let mut moves = all_moves(pos);
     if moves.is_empty() {
         let s = match pos.get_game_result() {
-            Status::Checkmate(_) => -color * (MATE_SCORE + depth as i16),
+            Status::Checkmate(_) => color * mated_in(ctx.ply),
             _ => 0,
         };
         return Some((color * s, Move::null()));
@@ -175,6 +176,7 @@ pub(crate) fn negamax(
         returned,
         best_move,
         ctx.generation,
+        ctx.ply,
     );
 
     Some((returned, best_move))
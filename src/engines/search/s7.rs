@@ -2,6 +2,7 @@ use std::time::Instant;
 use crate::attacks::movegen::all_moves;
 use crate::engines::constants::MAX_DEPTH;
 use crate::engines::engine_manager::Ctx;
+use crate::engines::score::mated_in;
 use crate::engines::transposition_table::Bound;
 use crate::mov::{Move, MoveList, MAX_MOVES};
 use crate::piece::Piece;
@@ -75,7 +76,6 @@ fn order_moves(pos: &Position,
 
 
 
-const MATE_SCORE: i16 = 10_000; // any value ≫ evaluate range
 
 
 pub(crate) fn negamax(
@@ -84,11 +84,12 @@ pub(crate) fn negamax(
     mut alpha: i16,
     beta: i16,
     color: i16,
-    deadline: Instant,
+    deadline: Option<Instant>,
     ctx: &mut Ctx,
 ) -> Option<(i16, Move)> {
+    ctx.nodes += 1;
     /* ----- 0. abort if out of time -------------------------------- */
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None; // bubble up timeout
     }
 
@@ -98,7 +99,7 @@ pub(crate) fn negamax(
     /* ----- 1. TT probe ------------------------------------------- */
     let mut hash_move = Move::null();
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         hash_move = e.mv;
         let entry_ok = e.depth >= depth;
 
@@ -123,6 +124,7 @@ pub(crate) fn negamax(
             sc,
             Move::null(),
             ctx.generation,
+            ctx.ply,
         );
         return Some((sc, Move::null()));
     }
@@ -131,7 +133,7 @@ pub(crate) fn negamax(
     let mut moves = all_moves(pos);
     if moves.is_empty() {
         let s = match pos.get_game_result() {
-            Status::Checkmate(_) => -color * (MATE_SCORE + depth as i16),
+            Status::Checkmate(_) => color * mated_in(ctx.ply),
             _ => 0,
         };
         return Some((color * s, Move::null()));
@@ -222,6 +224,7 @@ pub(crate) fn negamax(
         returned,
         best_move,
         ctx.generation,
+        ctx.ply,
     );
 
     Some((returned, best_move))
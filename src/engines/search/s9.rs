@@ -2,6 +2,7 @@ use std::time::Instant;
 use crate::attacks::movegen::all_moves;
 use crate::engines::constants::MAX_DEPTH;
 use crate::engines::engine_manager::Ctx;
+use crate::engines::score::mated_in;
 use crate::engines::transposition_table::Bound;
 use crate::mov::{Move, MoveList, MAX_MOVES};
 use crate::piece::Piece;
@@ -85,16 +86,16 @@ pub(crate) fn quiescence(
     mut alpha: i16,
     beta:      i16,
     color:     i16,
-    deadline:  Instant,
+    deadline:  Option<Instant>,
     ctx:       &mut Ctx,
 ) -> Option<(i16, Move)>
 {
     /* ---- 0.  time check --------------------------------------- */
-    if Instant::now() >= deadline { return None; }
+    if ctx.should_stop(deadline) { return None; }
     ctx.nodes += 1;
 
     /* ---- 1.  TT probe ----------------------------------------- */
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         // depth == 0 entries are quiescence results
         match e.bound {
             Bound::Exact          => return Some((e.score, e.mv)),
@@ -105,9 +106,9 @@ pub(crate) fn quiescence(
     }
 
     /* ---- 2.  stand-pat ---------------------------------------- */
-    let stand_pat = color * pos.evaluate();
+    let stand_pat = color * (ctx.eval_fn)(pos);
     if stand_pat >= beta {
-        ctx.tt.store(pos.zobrist(), 0, Bound::Lower, stand_pat, Move::null(), ctx.generation);
+        ctx.tt.store(pos.zobrist(), 0, Bound::Lower, stand_pat, Move::null(), ctx.generation, ctx.ply);
         return Some((beta, Move::null()));          // fail-high
     }
     if stand_pat > alpha { alpha = stand_pat; }
@@ -121,7 +122,7 @@ pub(crate) fn quiescence(
         }
     }
     if noisy.is_empty() {
-        ctx.tt.store(pos.zobrist(), 0, Bound::Exact, stand_pat, Move::null(), ctx.generation);
+        ctx.tt.store(pos.zobrist(), 0, Bound::Exact, stand_pat, Move::null(), ctx.generation, ctx.ply);
         return Some((stand_pat, Move::null()));
     }
 
@@ -142,7 +143,7 @@ pub(crate) fn quiescence(
         };
 
         if score >= beta {
-            ctx.tt.store(pos.zobrist(), 0, Bound::Lower, score, m, ctx.generation);
+            ctx.tt.store(pos.zobrist(), 0, Bound::Lower, score, m, ctx.generation, ctx.ply);
             return Some((beta, m));               // fail-high
         }
         if score > alpha {
@@ -157,14 +158,13 @@ pub(crate) fn quiescence(
     } else {
         Bound::Upper              // searched moves but didn’t reach beta
     };
-    ctx.tt.store(pos.zobrist(), 0, bound, alpha, best_move, ctx.generation);
+    ctx.tt.store(pos.zobrist(), 0, bound, alpha, best_move, ctx.generation, ctx.ply);
 
     Some((alpha, best_move))
 }
 
 
 
-const MATE_SCORE: i16 = 10_000; // any value ≫ evaluate range
 
 
 pub(crate) fn negamax(
@@ -173,11 +173,11 @@ pub(crate) fn negamax(
     mut alpha: i16,
     beta: i16,
     color: i16,
-    deadline: Instant,
+    deadline: Option<Instant>,
     ctx: &mut Ctx,
 ) -> Option<(i16, Move)> {
     /* ----- 0. abort if out of time -------------------------------- */
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None; // bubble up timeout
     }
 
@@ -225,7 +225,7 @@ pub(crate) fn negamax(
     /* ----- 1. TT probe ------------------------------------------- */
     let mut hash_move = Move::null();
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         hash_move = e.mv;
         let entry_ok = e.depth >= depth;
 
@@ -244,13 +244,14 @@ pub(crate) fn negamax(
     if depth == 0 {
         return quiescence(pos, alpha, beta, color, deadline, ctx);
     }
+    ctx.nodes += 1;
 
 
     /* ----- 3. generate moves & check terminal positions ----------- */
     let mut moves = all_moves(pos);
     if moves.is_empty() {
         let s = match pos.get_game_result() {
-            Status::Checkmate(_) => -color * (MATE_SCORE + depth as i16),
+            Status::Checkmate(_) => color * mated_in(ctx.ply),
             _ => 0,
         };
         return Some((color * s, Move::null()));
@@ -341,6 +342,7 @@ pub(crate) fn negamax(
         returned,
         best_move,
         ctx.generation,
+        ctx.ply,
     );
 
     Some((returned, best_move))
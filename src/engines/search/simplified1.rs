@@ -12,18 +12,19 @@ pub fn minimax(pos: &mut Position,
                a: i16,
                b: i16,
                _: i16,
-               deadline: Instant,
+               deadline: Option<Instant>,
                ctx: &mut Ctx)
 
             -> Option<(i16, Move)>
 {
+    ctx.nodes += 1;
 
     if depth == 0 {
         // leaf node
-        return Some((pos.evaluate(), Move::null()))
+        return Some(((ctx.eval_fn)(pos), Move::null()))
     }
 
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         // Ran out of time. End early.
         return None;
     }
@@ -33,7 +34,7 @@ pub fn minimax(pos: &mut Position,
 
     if mvs.is_empty() {
         // terminating node. Either checkmate or stalemate
-        return Some((pos.game_result_eval(depth), Move::null()))
+        return Some((pos.game_result_eval(ctx.ply), Move::null()))
     }
 
 
@@ -49,7 +50,9 @@ pub fn minimax(pos: &mut Position,
 
         for mv in mvs.iter() {
             pos.do_move(mv);
+            ctx.ply += 1;
             let reply = minimax(pos, depth - 1, a, b, 0, deadline, ctx);
+            ctx.ply -= 1;
             pos.undo_move();
 
             match reply {
@@ -72,7 +75,9 @@ pub fn minimax(pos: &mut Position,
 
         for mv in mvs.iter() {
             pos.do_move(mv);
+            ctx.ply += 1;
             let reply = minimax(pos, depth - 1, a, b, 0, deadline, ctx);
+            ctx.ply -= 1;
             pos.undo_move();
 
             match reply {
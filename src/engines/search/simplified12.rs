@@ -200,16 +200,17 @@ pub(crate) fn quiescence(
     mut alpha: i16,
     beta: i16,
     color: i16,
-    deadline: Instant,
+    deadline: Option<Instant>,
     ctx: &mut Ctx,
 ) -> Option<(i16, Move)> {
-    if Instant::now() >= deadline {
+    ctx.nodes += 1;
+    if ctx.should_stop(deadline) {
         return None;
     }
 
     let mut hash_mv = Move::null();
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         hash_mv = e.mv;
         match e.bound {
             Bound::Exact => return Some((e.score, e.mv)),
@@ -221,7 +222,7 @@ pub(crate) fn quiescence(
 
     let orig_alpha = alpha;
 
-    let stand_pat = color * pos.evaluate_2();
+    let stand_pat = color * (ctx.eval_fn)(pos);
     if stand_pat >= beta {
         ctx.tt.store(
             pos.zobrist(),
@@ -230,6 +231,7 @@ pub(crate) fn quiescence(
             stand_pat,
             Move::null(),
             ctx.generation,
+            ctx.ply,
         );
         // FAIL-SOFT: return the true score (not β)
         return Some((stand_pat, Move::null()));
@@ -240,7 +242,7 @@ pub(crate) fn quiescence(
 
     let all_mvs = all_moves(pos);
     if all_mvs.is_empty() {
-        return Some((color * pos.game_result_eval((MAX_DEPTH - ctx.ply) as u8), Move::null()));
+        return Some((color * pos.game_result_eval(ctx.ply), Move::null()));
     }
 
     let mvs = MovePicker::quiescence_mvs(pos, &all_mvs);
@@ -253,6 +255,7 @@ pub(crate) fn quiescence(
             stand_pat,
             Move::null(),
             ctx.generation,
+            ctx.ply,
         );
         return Some((stand_pat, Move::null()));
     }
@@ -275,7 +278,7 @@ pub(crate) fn quiescence(
         };
 
         if score >= beta {
-            ctx.tt.store(pos.zobrist(), 0, Bound::Lower, score, mv, ctx.generation);
+            ctx.tt.store(pos.zobrist(), 0, Bound::Lower, score, mv, ctx.generation, ctx.ply);
             // FAIL-SOFT: return the true score (not β)
             return Some((score, mv));
         }
@@ -287,7 +290,7 @@ pub(crate) fn quiescence(
 
     // With fail-soft, final bound depends on whether we improved over the original α
     let bound = if alpha <= orig_alpha { Bound::Upper } else { Bound::Exact };
-    ctx.tt.store(pos.zobrist(), 0, bound, alpha, best_move, ctx.generation);
+    ctx.tt.store(pos.zobrist(), 0, bound, alpha, best_move, ctx.generation, ctx.ply);
 
     Some((alpha, best_move))
 }
@@ -302,22 +305,23 @@ pub(crate) fn negamax(
     mut alpha: i16,
     beta: i16,
     color: i16,
-    deadline: Instant,
+    deadline: Option<Instant>,
     ctx: &mut Ctx,
 ) -> Option<(i16, Move)> {
     if (ctx.ply) >= MAX_DEPTH {
-        return Some((color * pos.evaluate_2(), Move::null()));
+        return Some((color * (ctx.eval_fn)(pos), Move::null()));
     }
 
     if depth == 0 {
         return quiescence(pos, alpha, beta, color, deadline, ctx);
     }
+    ctx.nodes += 1;
 
     if ctx.ply > 0 && (pos.half_move() >= 100 || pos.is_repeat_towards_three_fold_repetition()) {
         return Some((0, Move::null()));
     }
 
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None;
     }
 
@@ -355,7 +359,7 @@ pub(crate) fn negamax(
 
     let mut hash_mv = Move::null();
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         hash_mv = e.mv;
         let entry_ok = e.depth >= depth;
 
@@ -371,7 +375,7 @@ pub(crate) fn negamax(
 
     let mvs = all_moves(pos);
     if mvs.is_empty() {
-        return Some((color * pos.game_result_eval(depth), Move::null()));
+        return Some((color * pos.game_result_eval(ctx.ply), Move::null()));
     }
     let num_mvs = mvs.len;
 
@@ -468,7 +472,7 @@ pub(crate) fn negamax(
                     killers[0] = mv;
                 }
             }
-            ctx.tt.store(pos.zobrist(), depth, Bound::Lower, alpha, mv, ctx.generation);
+            ctx.tt.store(pos.zobrist(), depth, Bound::Lower, alpha, mv, ctx.generation, ctx.ply);
             break;
         }
     }
@@ -482,7 +486,7 @@ pub(crate) fn negamax(
     } else {
         Bound::Exact
     };
-    ctx.tt.store(pos.zobrist(), depth, bound, returned, best_move, ctx.generation);
+    ctx.tt.store(pos.zobrist(), depth, bound, returned, best_move, ctx.generation, ctx.ply);
     Some((returned, best_move))
 }
 
@@ -2,6 +2,9 @@ use std::time::Instant;
 use crate::attacks::movegen::all_moves;
 use crate::engines::constants::MAX_DEPTH;
 use crate::engines::engine_manager::Ctx;
+use crate::engines::score::mated_in;
+use crate::engines::search_params::SearchParams;
+use crate::engines::tablebase;
 use crate::engines::transposition_table::Bound;
 use crate::mov::{Move, MoveList, MAX_MOVES};
 use crate::piece::Piece::{Pawn, Queen};
@@ -195,8 +198,6 @@ impl MovePicker {
     }
 }
 
-const DELTA_MARGIN: i16 = 65;
-
 #[inline]
 fn optimistic_delta(pos: &Position, mv: Move) -> i16 {
     let mut delta = 0;
@@ -224,16 +225,17 @@ pub(crate) fn quiescence(
     mut alpha: i16,
     beta: i16,
     color: i16,
-    deadline: Instant,
+    deadline: Option<Instant>,
     ctx: &mut Ctx,
 ) -> Option<(i16, Move)> {
-    if Instant::now() >= deadline {
+    ctx.nodes += 1;
+    if ctx.should_stop(deadline) {
         return None;
     }
 
     let mut hash_mv = Move::null();
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         hash_mv = e.mv;
         match e.bound {
             Bound::Exact => return Some((e.score, e.mv)),
@@ -245,7 +247,7 @@ pub(crate) fn quiescence(
 
     let orig_alpha = alpha;
 
-    let stand_pat = color * pos.evaluate_2();
+    let stand_pat = color * (ctx.eval_fn)(pos);
     if stand_pat >= beta {
         ctx.tt.store(
             pos.zobrist(),
@@ -254,6 +256,7 @@ pub(crate) fn quiescence(
             stand_pat,
             Move::null(),
             ctx.generation,
+            ctx.ply,
         );
         return Some((stand_pat, Move::null()));
     }
@@ -263,7 +266,8 @@ pub(crate) fn quiescence(
 
     let all_mvs = all_moves(pos);
     if all_mvs.is_empty() {
-        return Some((color * pos.game_result_eval((MAX_DEPTH - ctx.ply) as u8), Move::null()));
+        let score = if pos.in_check() { mated_in(ctx.ply) } else { 0 };
+        return Some((score, Move::null()));
     }
 
 
@@ -277,6 +281,7 @@ pub(crate) fn quiescence(
             stand_pat,
             Move::null(),
             ctx.generation,
+            ctx.ply,
         );
         return Some((stand_pat, Move::null()));
     }
@@ -291,7 +296,7 @@ pub(crate) fn quiescence(
 
         if !pos.in_check() {
             let delta = optimistic_delta(pos, mv);
-            if stand_pat.saturating_add(delta).saturating_add(DELTA_MARGIN) <= alpha {
+            if stand_pat.saturating_add(delta).saturating_add(ctx.search_params.delta_margin as i16) <= alpha {
                 continue;
             }
         }
@@ -309,7 +314,7 @@ pub(crate) fn quiescence(
         };
 
         if score >= beta {
-            ctx.tt.store(pos.zobrist(), 0, Bound::Lower, score, mv, ctx.generation);
+            ctx.tt.store(pos.zobrist(), 0, Bound::Lower, score, mv, ctx.generation, ctx.ply);
             // FAIL-SOFT: return the true score (not β)
             return Some((score, mv));
         }
@@ -321,7 +326,7 @@ pub(crate) fn quiescence(
 
     // With fail-soft, final bound depends on whether we improved over the original α
     let bound = if alpha <= orig_alpha { Bound::Upper } else { Bound::Exact };
-    ctx.tt.store(pos.zobrist(), 0, bound, alpha, best_move, ctx.generation);
+    ctx.tt.store(pos.zobrist(), 0, bound, alpha, best_move, ctx.generation, ctx.ply);
 
     Some((alpha, best_move))
 }
@@ -331,29 +336,22 @@ fn do_null_move_pruning(depth: u8, pos: &mut Position) -> bool {
 }
 
 
-const FUT_MARGIN_1: i16 = 70; // for depth == 1
-const FUT_MARGIN_2: i16 = 70; // for depth == 2 (a bit larger)
 #[inline]
-fn fut_margin_for(depth: u8) -> i16 {
+fn fut_margin_for(depth: u8, params: &SearchParams) -> i16 {
     match depth {
-        0 | 1 => FUT_MARGIN_1,
-        2     => FUT_MARGIN_2,
+        0 | 1 => params.fut_margin_1 as i16,
+        2     => params.fut_margin_2 as i16,
         _     => 0,
     }
 }
 
 
-// Add near your other constants
-const RFP_MARGIN_1: i16 =  90;  // tune!
-const RFP_MARGIN_2: i16 = 150;  // tune!
-const RFP_MARGIN_3: i16 = 220;  // (optional if you use depth<=3)
-
 #[inline]
-fn rfp_margin_for(depth: u8) -> i16 {
+fn rfp_margin_for(depth: u8, params: &SearchParams) -> i16 {
     match depth {
-        1 => RFP_MARGIN_1,
-        2 => RFP_MARGIN_2,
-        _ => RFP_MARGIN_3, // or 0 if you limit to ≤2
+        1 => params.rfp_margin_1 as i16,
+        2 => params.rfp_margin_2 as i16,
+        _ => params.rfp_margin_3 as i16, // or 0 if you limit to ≤2
     }
 }
 
@@ -365,22 +363,23 @@ pub(crate) fn negamax(
     mut alpha: i16,
     beta: i16,
     color: i16,
-    deadline: Instant,
+    deadline: Option<Instant>,
     ctx: &mut Ctx,
 ) -> Option<(i16, Move)> {
     if (ctx.ply) >= MAX_DEPTH {
-        return Some((color * pos.evaluate_2(), Move::null()));
+        return Some((color * (ctx.eval_fn)(pos), Move::null()));
     }
 
     if depth == 0 {
         return quiescence(pos, alpha, beta, color, deadline, ctx);
     }
+    ctx.nodes += 1;
 
     if ctx.ply > 0 && (pos.half_move() >= 100 || pos.is_repeat_towards_three_fold_repetition()) {
         return Some((0, Move::null()));
     }
 
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         return None;
     }
 
@@ -395,7 +394,7 @@ pub(crate) fn negamax(
             ctx.ply += 1;
             let child = negamax(
                 pos,
-                nmp_reduction(depth),
+                nmp_reduction(depth, &ctx.search_params),
                 -beta,
                 -beta + 1,
                 -color,
@@ -418,7 +417,7 @@ pub(crate) fn negamax(
 
     let mut hash_mv = Move::null();
 
-    if let Some(e) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(e) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         hash_mv = e.mv;
         let entry_ok = e.depth >= depth;
 
@@ -433,15 +432,22 @@ pub(crate) fn negamax(
     }
 
 
+    // exact result from the tablebases after a capture or pawn move
+    if ctx.ply > 0 && let Some(wdl) = ctx.tablebase.as_ref().and_then(|tb| tb.probe_wdl(pos)) {
+        let score = tablebase::wdl_score(wdl, ctx.ply);
+        ctx.tt.store(pos.zobrist(), depth, Bound::Exact, score, Move::null(), ctx.generation, ctx.ply);
+        return Some((score, Move::null()));
+    }
+
     // ... after TT probe and before generating moves
-    let is_pv_node = beta - alpha > 1;
+    let is_pv_node = beta as i32 - alpha as i32 > 1;
     if depth <= 2 /* or 3 */
         && !pos.in_check()
         && !is_pv_node
         && ctx.ply > 0      // avoid at root
     {
-        let static_eval = color * pos.evaluate_2();
-        let margin = rfp_margin_for(depth);
+        let static_eval = color * (ctx.eval_fn)(pos);
+        let margin = rfp_margin_for(depth, &ctx.search_params);
 
         // If even a pessimistic drop still beats beta, cut
         if static_eval.saturating_sub(margin) >= beta {
@@ -453,6 +459,7 @@ pub(crate) fn negamax(
                 static_eval,
                 Move::null(),
                 ctx.generation,
+                ctx.ply,
             );
             return Some((static_eval, Move::null()));
         }
@@ -463,7 +470,8 @@ pub(crate) fn negamax(
 
     let mvs = all_moves(pos);
     if mvs.is_empty() {
-        return Some((color * pos.game_result_eval(depth), Move::null()));
+        let score = if pos.in_check() { mated_in(ctx.ply) } else { 0 };
+        return Some((score, Move::null()));
     }
     let num_mvs = mvs.len;
 
@@ -473,14 +481,14 @@ pub(crate) fn negamax(
 
     let mut best_move = Move::null();
     let mut best_score = i16::MIN + 1;
-    let is_pv_node = beta - alpha > 1;
+    let is_pv_node = beta as i32 - alpha as i32 > 1;
     let is_root = ctx.ply == 0;
 
     for i in 0..num_mvs {
         let mv = mv_picker.next(&ctx, pos);
 
         let static_eval = if !pos.in_check() {
-            color * pos.evaluate_2()
+            color * (ctx.eval_fn)(pos)
         } else {
             0 // unused if in_check; we won’t apply futility when in check
         };
@@ -494,7 +502,7 @@ pub(crate) fn negamax(
             && !mv.is_castling()
         {
             // Optional: very light optimism (e.g., king-safety/SEE) — but keep it minimal:
-            let margin = fut_margin_for(depth);
+            let margin = fut_margin_for(depth, &ctx.search_params);
             if static_eval.saturating_add(margin) <= alpha {
                 continue; // prune this quiet move
             }
@@ -506,7 +514,7 @@ pub(crate) fn negamax(
 
         let base = depth.saturating_sub(1);
         let ext  = extension(pos, mv);
-        let r = late_move_reduction(i, depth, ext);
+        let r = late_move_reduction(i, depth, ext, &ctx.search_params);
         let new_depth = base.saturating_add(ext).saturating_sub(r);
 
         let child = if i == 0 {
@@ -583,7 +591,7 @@ pub(crate) fn negamax(
                     killers[0] = mv;
                 }
             }
-            ctx.tt.store(pos.zobrist(), depth, Bound::Lower, alpha, mv, ctx.generation);
+            ctx.tt.store(pos.zobrist(), depth, Bound::Lower, alpha, mv, ctx.generation, ctx.ply);
             break;
         }
     }
@@ -597,19 +605,21 @@ pub(crate) fn negamax(
     } else {
         Bound::Exact
     };
-    ctx.tt.store(pos.zobrist(), depth, bound, returned, best_move, ctx.generation);
+    ctx.tt.store(pos.zobrist(), depth, bound, returned, best_move, ctx.generation, ctx.ply);
     Some((returned, best_move))
 }
 
-fn nmp_reduction(depth: u8) -> u8 {
-    let r_u8 = if depth <= 4 { 2 } else { (((depth as i16) - 4 + 2) / 3 + 2) as u8 };
+fn nmp_reduction(depth: u8, params: &SearchParams) -> u8 {
+    let r_u8 = (params.nmp_base + (depth as i32 - 2).max(0) / params.nmp_divisor) as u8;
     depth.saturating_sub(1 + r_u8)
 }
 
 
 #[inline]
-fn late_move_reduction(mv_num: usize, depth: u8, ext: u8) -> u8 {
-    let reduction = (0.0 + (depth as f32).ln() * (mv_num as f32).ln() / 3.14).floor() as u8;
+fn late_move_reduction(mv_num: usize, depth: u8, ext: u8, params: &SearchParams) -> u8 {
+    let base    = params.lmr_base as f32 / 100.0;
+    let divisor = params.lmr_divisor as f32 / 100.0;
+    let reduction = (base + (depth as f32).ln() * (mv_num as f32).ln() / divisor).floor() as u8;
     if reduction > 1 && ext == 1 {
         return reduction - 1
     }
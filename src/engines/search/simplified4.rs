@@ -124,17 +124,18 @@ pub fn minimax(pos: &mut Position,
                mut a: i16,
                mut b: i16,
                _: i16,
-               deadline: Instant,
+               deadline: Option<Instant>,
                ctx: &mut Ctx)
 
                -> Option<(i16, Move)>
 {
+    ctx.nodes += 1;
     if depth == 0 {
         // leaf node
-        return Some((pos.evaluate(), Move::null()))
+        return Some(((ctx.eval_fn)(pos), Move::null()))
     }
 
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         // Ran out of time. End early.
         return None;
     }
@@ -145,7 +146,7 @@ pub fn minimax(pos: &mut Position,
 
     if mvs.is_empty() {
         // terminal node. checkmate or stalemate
-        return Some((pos.game_result_eval(depth), Move::null()))
+        return Some((pos.game_result_eval(ctx.ply), Move::null()))
     }
 
     let mut top_mv: Move = Move::null();
@@ -125,19 +125,20 @@ pub fn minimax(pos: &mut Position,
                mut a: i16,
                mut b: i16,
                _: i16,
-               deadline: Instant,
+               deadline: Option<Instant>,
                ctx: &mut Ctx)
 
                -> Option<(i16, Move)>
 {
+    ctx.nodes += 1;
     if depth == 0 {
         // leaf node
-        return Some((pos.evaluate(), Move::null()))
+        return Some(((ctx.eval_fn)(pos), Move::null()))
     }
 
     let mut hash_move = Move::null();
 
-    if let Some(tt_entry) = ctx.tt.probe(pos.zobrist()) {
+    if let Some(tt_entry) = ctx.tt.probe(pos.zobrist(), ctx.ply) {
         hash_move = tt_entry.mv;
         let entry_ok = tt_entry.depth >= depth;
 
@@ -152,7 +153,7 @@ pub fn minimax(pos: &mut Position,
         }
     }
 
-    if Instant::now() >= deadline {
+    if ctx.should_stop(deadline) {
         // Ran out of time. End early.
         return None;
     }
@@ -166,7 +167,7 @@ pub fn minimax(pos: &mut Position,
 
     if mvs.is_empty() {
         // terminal node. checkmate or stalemate
-        return Some((pos.game_result_eval(depth), Move::null()))
+        return Some((pos.game_result_eval(ctx.ply), Move::null()))
     }
 
     let mut top_mv: Move = Move::null();
@@ -212,7 +213,7 @@ pub fn minimax(pos: &mut Position,
         }
 
         let bound = if a <= a0 { Bound::Upper } else if a >= b0 { Bound::Lower } else { Bound::Exact };
-        ctx.tt.store(pos.zobrist(), depth, bound, a, top_mv, ctx.generation);
+        ctx.tt.store(pos.zobrist(), depth, bound, a, top_mv, ctx.generation, ctx.ply);
 
         Some((a, top_mv))
     } else {
@@ -250,7 +251,7 @@ pub fn minimax(pos: &mut Position,
         }
 
         let bound = if b <= a0 { Bound::Upper } else if b >= b0 { Bound::Lower } else { Bound::Exact };
-        ctx.tt.store(pos.zobrist(), depth, bound, b, top_mv, ctx.generation);
+        ctx.tt.store(pos.zobrist(), depth, bound, b, top_mv, ctx.generation, ctx.ply);
 
 
         Some((b, top_mv))
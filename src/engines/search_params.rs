@@ -0,0 +1,96 @@
+/// Bounds and SPSA step of one search parameter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub min:  i32,
+    pub max:  i32,
+    pub step: i32,  // perturbation SPSA ends its run with
+}
+
+/// Every parameter, in the order of `SearchParams::values`.
+pub const PARAM_SPECS: [ParamSpec; 10] = [
+    ParamSpec { name: "FutMargin1",  min: 0,   max: 300,  step: 10 },
+    ParamSpec { name: "FutMargin2",  min: 0,   max: 400,  step: 10 },
+    ParamSpec { name: "RfpMargin1",  min: 0,   max: 400,  step: 10 },
+    ParamSpec { name: "RfpMargin2",  min: 0,   max: 500,  step: 15 },
+    ParamSpec { name: "RfpMargin3",  min: 0,   max: 600,  step: 20 },
+    ParamSpec { name: "DeltaMargin", min: 0,   max: 400,  step: 10 },
+    ParamSpec { name: "LmrBase",     min: -100, max: 200, step: 10 },
+    ParamSpec { name: "LmrDivisor",  min: 100, max: 600,  step: 20 },
+    ParamSpec { name: "NmpBase",     min: 1,   max: 5,    step: 1 },
+    ParamSpec { name: "NmpDivisor",  min: 1,   max: 8,    step: 1 },
+];
+
+
+/// The pruning margins and reduction formulas of simplified15, settable at run time so
+/// they can be tuned (see `simulator::spsa`) without rebuilding.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SearchParams {
+    pub fut_margin_1: i32,  // futility pruning at depth 1
+    pub fut_margin_2: i32,  // and at depth 2
+    pub rfp_margin_1: i32,  // reverse futility pruning at depth 1
+    pub rfp_margin_2: i32,
+    pub rfp_margin_3: i32,
+    pub delta_margin: i32,  // quiescence delta pruning
+    pub lmr_base:     i32,  // LMR: base + ln(depth) * ln(move number) / divisor, in hundredths
+    pub lmr_divisor:  i32,
+    pub nmp_base:     i32,  // null move: R = base + (depth - 2) / divisor
+    pub nmp_divisor:  i32,
+}
+
+impl Default for SearchParams {
+    fn default() -> Self {
+        SearchParams {
+            fut_margin_1: 70,
+            fut_margin_2: 70,
+            rfp_margin_1: 90,
+            rfp_margin_2: 150,
+            rfp_margin_3: 220,
+            delta_margin: 65,
+            lmr_base:     0,
+            lmr_divisor:  314,
+            nmp_base:     2,
+            nmp_divisor:  3,
+        }
+    }
+}
+
+impl SearchParams {
+    /// The values in `PARAM_SPECS` order.
+    pub fn values(&self) -> [i32; PARAM_SPECS.len()] {
+        [
+            self.fut_margin_1, self.fut_margin_2,
+            self.rfp_margin_1, self.rfp_margin_2, self.rfp_margin_3,
+            self.delta_margin,
+            self.lmr_base, self.lmr_divisor,
+            self.nmp_base, self.nmp_divisor,
+        ]
+    }
+
+    /// Parameters from values in `PARAM_SPECS` order, clamped to their bounds.
+    pub fn from_values(values: [i32; PARAM_SPECS.len()]) -> SearchParams {
+        let [fut_margin_1, fut_margin_2, rfp_margin_1, rfp_margin_2, rfp_margin_3, delta_margin,
+             lmr_base, lmr_divisor, nmp_base, nmp_divisor] =
+            std::array::from_fn(|i| values[i].clamp(PARAM_SPECS[i].min, PARAM_SPECS[i].max));
+        SearchParams {
+            fut_margin_1, fut_margin_2, rfp_margin_1, rfp_margin_2, rfp_margin_3, delta_margin,
+            lmr_base, lmr_divisor, nmp_base, nmp_divisor,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<i32> {
+        let i = PARAM_SPECS.iter().position(|spec| spec.name.eq_ignore_ascii_case(name))?;
+        Some(self.values()[i])
+    }
+
+    /// Sets a parameter by name, clamped to its bounds. Returns `false` for an unknown name.
+    pub fn set(&mut self, name: &str, value: i32) -> bool {
+        let Some(i) = PARAM_SPECS.iter().position(|spec| spec.name.eq_ignore_ascii_case(name)) else {
+            return false;
+        };
+        let mut values = self.values();
+        values[i] = value;
+        *self = SearchParams::from_values(values);
+        true
+    }
+}
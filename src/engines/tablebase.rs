@@ -0,0 +1,179 @@
+use std::io;
+#[cfg(feature = "syzygy")]
+use std::num::NonZeroU32;
+#[cfg(feature = "syzygy")]
+use shakmaty::{Bitboard, Board, CastlingMode, Chess, FromSetup, Role, Setup, Square};
+#[cfg(feature = "syzygy")]
+use shakmaty_syzygy::Tablebase as Tables;
+use crate::color::Color;
+use crate::engines::score::TB_WIN;
+use crate::mov::Move;
+#[cfg(feature = "syzygy")]
+use crate::piece::Piece;
+use crate::position::Position;
+#[cfg(feature = "syzygy")]
+use crate::position::NO_SQ;
+
+/// Separates several directories in a tablebase path, as in other engines' `SyzygyPath`.
+#[cfg(all(feature = "syzygy", windows))]
+const PATH_SEPARATOR: char = ';';
+#[cfg(all(feature = "syzygy", not(windows)))]
+const PATH_SEPARATOR: char = ':';
+
+
+/// Result for the side to move. Cursed wins and blessed losses are spoiled by the 50-move rule.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Wdl {
+    Loss,
+    BlessedLoss,
+    Draw,
+    CursedWin,
+    Win,
+}
+
+#[cfg(feature = "syzygy")]
+impl From<shakmaty_syzygy::Wdl> for Wdl {
+    fn from(wdl: shakmaty_syzygy::Wdl) -> Wdl {
+        match wdl {
+            shakmaty_syzygy::Wdl::Loss        => Wdl::Loss,
+            shakmaty_syzygy::Wdl::BlessedLoss => Wdl::BlessedLoss,
+            shakmaty_syzygy::Wdl::Draw        => Wdl::Draw,
+            shakmaty_syzygy::Wdl::CursedWin   => Wdl::CursedWin,
+            shakmaty_syzygy::Wdl::Win         => Wdl::Win,
+        }
+    }
+}
+
+
+/// What the tablebases say about the root: the move that keeps the best result while making
+/// the quickest progress towards the next capture or pawn move.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RootProbe {
+    pub mv:  Move,
+    pub wdl: Wdl,  // for the side to move, taking the half-move clock into account
+    pub dtz: i32,  // distance to zeroing in plies, negative when losing
+}
+
+/// Syzygy endgame tablebases loaded from `.rtbw` (WDL) and `.rtbz` (DTZ) files. Probing goes
+/// through `shakmaty-syzygy` (GPL-3.0-or-later), so it is only built with the `syzygy` cargo
+/// feature; without it no tables ever load.
+pub struct Tablebase {
+    #[cfg(feature = "syzygy")]
+    tables: Tables<Chess>,
+}
+
+impl Tablebase {
+    /// Loads every table found in `path`, which may list several directories.
+    #[cfg(feature = "syzygy")]
+    pub fn open(path: &str) -> io::Result<Tablebase> {
+        let mut tables = Tables::new();
+        for dir in path.split(PATH_SEPARATOR).map(str::trim).filter(|dir| !dir.is_empty()) {
+            tables.add_directory(dir)?;
+        }
+        Ok(Tablebase { tables })
+    }
+
+    #[cfg(not(feature = "syzygy"))]
+    pub fn open(_path: &str) -> io::Result<Tablebase> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "built without the `syzygy` feature"))
+    }
+
+    /// Most pieces (kings included) of any loaded table; 0 when none were found.
+    #[cfg(feature = "syzygy")]
+    pub fn max_pieces(&self) -> usize {
+        self.tables.max_pieces()
+    }
+
+    #[cfg(not(feature = "syzygy"))]
+    pub fn max_pieces(&self) -> usize {
+        0
+    }
+
+    fn covers(&self, pos: &Position) -> bool {
+        pos.occupied().count_ones() as usize <= self.max_pieces()
+            && ![Color::White, Color::Black].iter().any(|&c| pos.kingside(c) || pos.queenside(c))
+    }
+
+    /// WDL of `pos` for the side to move. Only answers right after a capture or pawn move
+    /// (half-move clock 0), where the WDL tables alone are exact under the 50-move rule.
+    pub fn probe_wdl(&self, pos: &Position) -> Option<Wdl> {
+        if pos.half_move() != 0 || !self.covers(pos) {
+            return None;
+        }
+        self.wdl_after_zeroing(pos)
+    }
+
+    /// Picks a root move from the DTZ tables. Needs both WDL and DTZ files.
+    pub fn probe_root(&self, pos: &Position) -> Option<RootProbe> {
+        if !self.covers(pos) {
+            return None;
+        }
+        self.root(pos)
+    }
+
+    #[cfg(feature = "syzygy")]
+    fn wdl_after_zeroing(&self, pos: &Position) -> Option<Wdl> {
+        self.tables.probe_wdl_after_zeroing(&to_chess(pos)?).ok().map(Wdl::from)
+    }
+
+    #[cfg(feature = "syzygy")]
+    fn root(&self, pos: &Position) -> Option<RootProbe> {
+        let chess = to_chess(pos)?;
+        let (mv, _) = self.tables.best_move(&chess).ok()??;
+        let wdl = self.tables.probe_wdl(&chess).ok()?.after_zeroing().into();
+        let dtz = self.tables.probe_dtz(&chess).ok()?.ignore_rounding().0;
+        let mv  = pos.parse_uci_move(&mv.to_uci(CastlingMode::Standard).to_string()).ok()?;
+        Some(RootProbe { mv, wdl, dtz })
+    }
+
+    #[cfg(not(feature = "syzygy"))]
+    fn wdl_after_zeroing(&self, _pos: &Position) -> Option<Wdl> {
+        None
+    }
+
+    #[cfg(not(feature = "syzygy"))]
+    fn root(&self, _pos: &Position) -> Option<RootProbe> {
+        None
+    }
+}
+
+/// Search score of a tablebase result `ply` half-moves from the root. Wins rank below any
+/// mate the search finds; results spoiled by the 50-move rule count as (nearly) drawn.
+pub fn wdl_score(wdl: Wdl, ply: u16) -> i16 {
+    match wdl {
+        Wdl::Win         => TB_WIN - ply as i16,
+        Wdl::CursedWin   => 1,
+        Wdl::Draw        => 0,
+        Wdl::BlessedLoss => -1,
+        Wdl::Loss        => -TB_WIN + ply as i16,
+    }
+}
+
+/// The same position for the prober, built from the piece lists.
+#[cfg(feature = "syzygy")]
+fn to_chess(pos: &Position) -> Option<Chess> {
+    let mut board = Board::empty();
+    for (color, tb_color) in [(Color::White, shakmaty::Color::White), (Color::Black, shakmaty::Color::Black)] {
+        for (i, role) in Role::ALL.into_iter().enumerate() {
+            let (squares, count) = pos.piece_list(Piece::from(i), color);
+            for &sq in &squares[..count] {
+                board.set_piece_at(Square::new(sq as u32), role.of(tb_color));
+            }
+        }
+    }
+
+    let mut setup = Setup::empty();
+    setup.board = board;
+    setup.turn = if pos.side_to_move().is_white() { shakmaty::Color::White } else { shakmaty::Color::Black };
+    setup.castling_rights = [
+        (pos.kingside(Color::White),  Square::H1),
+        (pos.queenside(Color::White), Square::A1),
+        (pos.kingside(Color::Black),  Square::H8),
+        (pos.queenside(Color::Black), Square::A8),
+    ].into_iter().filter(|&(has, _)| has).fold(Bitboard::EMPTY, |bb, (_, sq)| bb | Bitboard::from_square(sq));
+    setup.ep_square = (pos.en_passant() != NO_SQ).then(|| Square::new(pos.en_passant() as u32));
+    setup.halfmoves = pos.half_move() as u32;
+    setup.fullmoves = NonZeroU32::new(pos.full_move() as u32).unwrap_or(NonZeroU32::MIN);
+
+    Chess::from_setup(setup, CastlingMode::Standard).ok()
+}
@@ -0,0 +1,146 @@
+use std::time::{Duration, Instant};
+use crate::mov::Move;
+
+/// Milliseconds kept back for GUI / process lag unless told otherwise.
+pub const DEFAULT_MOVE_OVERHEAD: u64 = 20;
+/// Moves assumed to be left in the game when the clock does not say.
+const DEFAULT_MOVES_TO_GO: u64 = 30;
+/// The hard limit may be at most this many times the soft one ...
+const HARD_FACTOR: u64 = 4;
+/// ... and never more than this fraction (in percent) of what is left on the clock.
+const MAX_CLOCK_PERCENT: u64 = 80;
+
+/// A root score loss (in centipawns) between iterations that makes the search think longer.
+const SCORE_DROP_MARGIN: i16 = 25;
+/// Soft limit scale by number of iterations in a row that kept the same best move.
+const STABILITY_SCALE: [f32; 5] = [1.5, 1.2, 1.0, 0.8, 0.6];
+
+
+/// One side's clock when it has to move. Times are in milliseconds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Clock {
+    pub remaining:   u64,
+    pub increment:   u64,
+    pub moves_to_go: Option<u64>,  // moves until the next time control, `None` for sudden death
+}
+
+impl Clock {
+    pub fn new(remaining: u64, increment: u64) -> Clock {
+        Clock { remaining, increment, moves_to_go: None }
+    }
+
+    /// Charges `used_ms` for a move and adds the increment. Returns `false` if the flag fell.
+    pub fn punch(&mut self, used_ms: u64) -> bool {
+        if used_ms > self.remaining {
+            self.remaining = 0;
+            return false;
+        }
+        self.remaining = self.remaining - used_ms + self.increment;
+        true
+    }
+}
+
+
+/// Decides how long one search may run.
+///
+/// The hard deadline is what the search functions abort on. The soft limit is checked
+/// between iterative-deepening iterations: it shrinks while the best move stays the same
+/// and grows when the root score drops, but never past the hard deadline.
+pub struct TimeManager {
+    start:    Instant,
+    soft:     Duration,
+    hard:     Duration,
+    adaptive: bool,     // `false` for a fixed time per move, which is always used in full
+
+    best:       Move,
+    stable:     usize,  // iterations in a row that returned `best`
+    last_score: Option<i16>,
+    drop_scale: f32,
+}
+
+impl TimeManager {
+    /// Searches for exactly `ms` milliseconds (the old `time_ms` behaviour).
+    pub fn fixed(ms: u64) -> TimeManager {
+        let limit = Duration::from_millis(ms);
+        Self::with_limits(limit, limit, false)
+    }
+
+    /// Budgets one move out of `clock`, keeping `overhead_ms` back for communication lag.
+    pub fn new(clock: Clock, overhead_ms: u64) -> TimeManager {
+        let available  = clock.remaining.saturating_sub(overhead_ms).max(1);
+        let moves_left = clock.moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+
+        let hard = (available / moves_left + clock.increment)
+            .saturating_mul(HARD_FACTOR)
+            .min(available * MAX_CLOCK_PERCENT / 100)
+            .max(1);
+        let soft = (available / moves_left + clock.increment * 3 / 4).clamp(1, hard);
+
+        Self::with_limits(Duration::from_millis(soft), Duration::from_millis(hard), true)
+    }
+
+    /// No time limit; the search runs until some other limit or the stop flag ends it.
+    pub fn infinite() -> TimeManager {
+        Self::with_limits(Duration::MAX, Duration::MAX, false)
+    }
+
+    fn with_limits(soft: Duration, hard: Duration, adaptive: bool) -> TimeManager {
+        TimeManager {
+            start: Instant::now(),
+            soft,
+            hard,
+            adaptive,
+            best: Move::null(),
+            stable: 0,
+            last_score: None,
+            drop_scale: 1.0,
+        }
+    }
+
+    pub fn soft_limit(&self) -> Duration {
+        self.soft
+    }
+
+    pub fn hard_limit(&self) -> Duration {
+        self.hard
+    }
+
+    /// Point in time at which the search functions must give up, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.start.checked_add(self.hard)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// The soft limit scaled by best-move stability and score trend.
+    pub fn limit(&self) -> Duration {
+        if !self.adaptive {
+            return self.hard;
+        }
+        let stability = STABILITY_SCALE[self.stable.min(STABILITY_SCALE.len() - 1)];
+        self.soft.mul_f32(stability * self.drop_scale).min(self.hard)
+    }
+
+    /// Records a finished iteration. Returns `true` if no further iteration should be started.
+    pub fn iteration_done(&mut self, best: Move, score: i16) -> bool {
+        if best == self.best {
+            self.stable += 1;
+        } else {
+            self.best   = best;
+            self.stable = 0;
+        }
+
+        // a falling score means the position is harder than it looked: allow up to twice the time
+        let drop = self.last_score.map_or(0, |last| last as i32 - score as i32);
+        self.drop_scale = if drop > SCORE_DROP_MARGIN as i32 {
+            (1.0 + drop as f32 / 100.0).min(2.0)
+        } else {
+            1.0
+        };
+        self.last_score = Some(score);
+
+        self.elapsed() >= self.limit()
+    }
+}
@@ -1,6 +1,13 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::engines::score;
 use crate::mov::Move;
 
 const CLUSTER_SIZE: usize = 4;
+pub const DEFAULT_HASH_MB: usize = 128;
+/// Depth one search of age is worth when picking an entry to overwrite.
+const AGE_WEIGHT: i32 = 4;
+/// Slots looked at by `hashfull`.
+const HASHFULL_SAMPLE: usize = 1000;
 
 
 #[derive(Copy, Clone, Default, PartialEq)]
@@ -12,74 +19,159 @@ pub enum Bound {
     Upper
 }
 
+impl Bound {
+    fn from_bits(bits: u64) -> Bound {
+        match bits {
+            1 => Bound::Lower,
+            2 => Bound::Upper,
+            _ => Bound::Exact,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Default)]
-#[repr(C)]
 pub struct Entry {
-    lock:   u16,   // high 16 bits of zobrist
     pub(crate) depth:  u8,
     pub(crate) bound: Bound,   // <-- was u8 flag
     pub(crate) score:  i16,
     pub(crate) mv:     Move,
-    age:    u16,    // optional: generation counter
+    age:    u16,    // `Ctx::generation` of the search that wrote it
+}
+
+// Entry layout inside one u64: | age 16 | bound 8 | depth 8 | score 16 | move 16 |
+impl Entry {
+    #[inline(always)]
+    fn pack(&self) -> u64 {
+        self.mv.raw() as u64
+            | (self.score as u16 as u64) << 16
+            | (self.depth as u64) << 32
+            | (self.bound as u64) << 40
+            | (self.age as u64) << 48
+    }
+
+    #[inline(always)]
+    fn unpack(data: u64) -> Entry {
+        Entry {
+            mv:    Move::new(data as u16),
+            score: (data >> 16) as u16 as i16,
+            depth: (data >> 32) as u8,
+            bound: Bound::from_bits((data >> 40) & 0xFF),
+            age:   (data >> 48) as u16,
+        }
+    }
+}
+
+/// One slot. The key is stored XORed with the data, so a slot torn by two threads writing
+/// at once no longer verifies against either hash and is simply treated as a miss.
+#[derive(Default)]
+struct Slot {
+    key:  AtomicU64,   // zobrist ^ data
+    data: AtomicU64,
+}
+
+impl Slot {
+    #[inline(always)]
+    fn load(&self) -> (u64, u64) {
+        let data = self.data.load(Ordering::Relaxed);
+        (self.key.load(Ordering::Relaxed) ^ data, data)
+    }
+
+    #[inline(always)]
+    fn write(&self, hash: u64, entry: Entry) {
+        let data = entry.pack();
+        self.key.store(hash ^ data, Ordering::Relaxed);
+        self.data.store(data, Ordering::Relaxed);
+    }
+
+    fn clear(&self) {
+        self.key.store(0, Ordering::Relaxed);
+        self.data.store(0, Ordering::Relaxed);
+    }
 }
 
+/// Shared between search threads: all methods take `&self` and never lock.
 pub struct TransTable {
     mask: usize,
-    data: Box<[Entry]>,     // length = clusters * CLUSTER_SIZE
+    data: Box<[Slot]>,     // length = clusters * CLUSTER_SIZE
 }
 
 impl TransTable {
     pub fn new(mb: usize) -> Self {
         let bytes   = mb * 1024 * 1024;
-        let clusters = bytes / (std::mem::size_of::<Entry>() * CLUSTER_SIZE);
+        let clusters = bytes / (std::mem::size_of::<Slot>() * CLUSTER_SIZE);
         let pow2     = clusters.next_power_of_two();
         let size     = pow2 * CLUSTER_SIZE;
 
-        let data = vec![Entry::default(); size].into_boxed_slice();
+        let data = (0..size).map(|_| Slot::default()).collect();
         Self { mask: pow2 - 1, data }
     }
 
-    #[inline(always)]
-    fn cluster(&self, hash: u64) -> &[Entry] {
-        let idx = (hash as usize & self.mask) * CLUSTER_SIZE;
-        &self.data[idx..idx + CLUSTER_SIZE]
+    /// Empties every slot. Only meant for when no search is running.
+    pub fn clear(&self) {
+        self.data.iter().for_each(Slot::clear);
+    }
+
+    /// How full the table is, in permille: the share of sampled slots holding an entry
+    /// written by the search of `generation`.
+    pub fn hashfull(&self, generation: u16) -> u16 {
+        let sample = &self.data[..HASHFULL_SAMPLE.min(self.data.len())];
+        let used = sample.iter()
+            .map(|slot| slot.data.load(Ordering::Relaxed))
+            .filter(|&data| data != 0 && Entry::unpack(data).age == generation)
+            .count();
+        (used * 1000 / sample.len()) as u16
     }
 
-    // replace _worst_ entry in cluster (simple depth‑pref replacement)
     #[inline(always)]
-    fn cluster_mut(&mut self, hash: u64) -> &mut [Entry] {
+    fn cluster(&self, hash: u64) -> &[Slot] {
         let idx = (hash as usize & self.mask) * CLUSTER_SIZE;
-        &mut self.data[idx..idx + CLUSTER_SIZE]
+        &self.data[idx..idx + CLUSTER_SIZE]
     }
 
 
+    /// `ply` is the distance from the root; mate scores are stored relative to this node.
     #[inline(always)]
-    pub(crate) fn store(&mut self, hash: u64, depth: u8, bound: Bound, score: i16, mv: Move, age: u16) {
-        let lock = (hash >> 48) as u16;
-        let cluster = self.cluster_mut(hash);
-
-        // Replace same lock if found
-        if let Some(e) = cluster.iter_mut().find(|e| e.lock == lock) {
-            if depth >= e.depth { *e = Entry { lock, depth, bound, score, mv, age }; }
-            return;
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn store(&self, hash: u64, depth: u8, bound: Bound, score: i16, mv: Move, age: u16, ply: u16) {
+        // The older searches store their +-i16::MAX window bounds as well. Those are no mate:
+        // they become the largest non-mate score, which is still a valid (if looser) bound.
+        let score = if (-score::MATE..=score::MATE).contains(&score) {
+            score::to_tt(score, ply)
+        } else {
+            score.clamp(-score::MATE_BOUND + 1, score::MATE_BOUND - 1)
+        };
+        let entry = Entry { depth, bound, score, mv, age };
+        let cluster = self.cluster(hash);
+
+        // Replace same position if found; an entry from an earlier search always gives way
+        for slot in cluster {
+            let (key, data) = slot.load();
+            if key == hash {
+                let old = Entry::unpack(data);
+                if depth >= old.depth || old.age != age { slot.write(hash, entry); }
+                return;
+            }
         }
-        // else choose the entry with smallest (depth, age) tuple
-        let idx = cluster.iter()
-            .enumerate()
-            .min_by_key(|(_, e)| (e.depth, e.age))
-            .map(|(i, _)| i).unwrap();
-
-        cluster[idx] = Entry { lock, depth, bound, score, mv, age };
-
+        // else evict the least useful entry: shallow, and left over from older searches
+        let victim = cluster.iter()
+            .min_by_key(|slot| {
+                let e = Entry::unpack(slot.load().1);
+                e.depth as i32 - AGE_WEIGHT * age.wrapping_sub(e.age) as i32
+            })
+            .unwrap();
+
+        victim.write(hash, entry);
     }
 
 
+    /// Mate scores come back relative to the root again, as seen from `ply`.
     #[inline(always)]
-    pub(crate) fn probe(&self, hash: u64) -> Option<Entry> {
-        let lock = (hash >> 48) as u16;
-        for e in self.cluster(hash) {
-            if e.lock == lock {
-                return Some(*e);   // depth check is removed
+    pub(crate) fn probe(&self, hash: u64, ply: u16) -> Option<Entry> {
+        for slot in self.cluster(hash) {
+            let (key, data) = slot.load();
+            if key == hash {
+                let e = Entry::unpack(data);
+                return Some(Entry { score: score::from_tt(e.score, ply), ..e });   // depth check is removed
             }
         }
         None
@@ -1,4 +1,5 @@
 use crate::color::Color;
+use crate::eval_params::EVAL_PARAMS;
 use crate::piece::{Piece, PIECE_SCORES};
 use crate::position::Position;
 
@@ -11,139 +12,90 @@ pub struct EvalCache {
     pub(crate) phase: i32,        // 24 → opening, … 0 → pure endings
 }
 
-// Pawn, Knight, …, King PSTs (white’s view)
-pub const MG_PAWN_TABLE: [i16; 64] = [
-0,   0,   0,   0,   0,   0,  0,   0,
-98, 134,  61,  95,  68, 126, 34, -11,
--6,   7,  26,  31,  65,  56, 25, -20,
--14,  13,   6,  21,  23,  12, 17, -23,
--27,  -2,  -5,  12,  17,   6, 10, -25,
--26,  -4,  -4, -10,   3,   3, 33, -12,
--35,  -1, -20, -23, -15,  24, 38, -22,
-0,   0,   0,   0,   0,   0,  0,   0,
-];
-
-pub const EG_PAWN_TABLE: [i16; 64] = [
-0,   0,   0,   0,   0,   0,   0,   0,
-178, 173, 158, 134, 147, 132, 165, 187,
-94, 100,  85,  67,  56,  53,  82,  84,
-32,  24,  13,   5,  -2,   4,  17,  17,
-13,   9,  -3,  -7,  -7,  -8,   3,  -1,
-4,   7,  -6,   1,   0,  -5,  -1,  -8,
-13,   8,   8,  10,  13,   0,   2,  -7,
-0,   0,   0,   0,   0,   0,   0,   0,
-];
-
-pub const MG_KNIGHT_TABLE: [i16; 64] = [
--167, -89, -34, -49,  61, -97, -15, -107,
--73, -41,  72,  36,  23,  62,   7,  -17,
--47,  60,  37,  65,  84, 129,  73,   44,
--9,  17,  19,  53,  37,  69,  18,   22,
--13,   4,  16,  13,  28,  19,  21,   -8,
--23,  -9,  12,  10,  19,  17,  25,  -16,
--29, -53, -12,  -3,  -1,  18, -14,  -19,
--105, -21, -58, -33, -17, -28, -19,  -23,
-];
-
-pub const EG_KNIGHT_TABLE: [i16; 64] = [
--58, -38, -13, -28, -31, -27, -63, -99,
--25,  -8, -25,  -2,  -9, -25, -24, -52,
--24, -20,  10,   9,  -1,  -9, -19, -41,
--17,   3,  22,  22,  22,  11,   8, -18,
--18,  -6,  16,  25,  16,  17,   4, -18,
--23,  -3,  -1,  15,  10,  -3, -20, -22,
--42, -20, -10,  -5,  -2, -20, -23, -44,
--29, -51, -23, -15, -22, -18, -50, -64,
-];
-
-pub const MG_BISHOP_TABLE: [i16; 64] = [
--29,   4, -82, -37, -25, -42,   7,  -8,
--26,  16, -18, -13,  30,  59,  18, -47,
--16,  37,  43,  40,  35,  50,  37,  -2,
--4,   5,  19,  50,  37,  37,   7,  -2,
--6,  13,  13,  26,  34,  12,  10,   4,
-0,  15,  15,  15,  14,  27,  18,  10,
-4,  15,  16,   0,   7,  21,  33,   1,
--33,  -3, -14, -21, -13, -12, -39, -21,
-];
-
-pub const EG_BISHOP_TABLE: [i16; 64] = [
--14, -21, -11,  -8, -7,  -9, -17, -24,
--8,  -4,   7, -12, -3, -13,  -4, -14,
-2,  -8,   0,  -1, -2,   6,   0,   4,
--3,   9,  12,   9, 14,  10,   3,   2,
--6,   3,  13,  19,  7,  10,  -3,  -9,
--12,  -3,   8,  10, 13,   3,  -7, -15,
--14, -18,  -7,  -1,  4,  -9, -15, -27,
--23,  -9, -23,  -5, -9, -16,  -5, -17,
-];
-
-pub const MG_ROOK_TABLE: [i16; 64] = [
-32,  42,  32,  51, 63,  9,  31,  43,
-27,  32,  58,  62, 80, 67,  26,  44,
--5,  19,  26,  36, 17, 45,  61,  16,
--24, -11,   7,  26, 24, 35,  -8, -20,
--36, -26, -12,  -1,  9, -7,   6, -23,
--45, -25, -16, -17,  3,  0,  -5, -33,
--44, -16, -20,  -9, -1, 11,  -6, -71,
--19, -13,   1,  17, 16,  7, -37, -26,
-];
-
-pub const EG_ROOK_TABLE: [i16; 64] = [
-13, 10, 18, 15, 12,  12,   8,   5,
-11, 13, 13, 11, -3,   3,   8,   3,
-7,  7,  7,  5,  4,  -3,  -5,  -3,
-4,  3, 13,  1,  2,   1,  -1,   2,
-3,  5,  8,  4, -5,  -6,  -8, -11,
--4,  0, -5, -1, -7, -12,  -8, -16,
--6, -6,  0,  2, -9,  -9, -11,  -3,
--9,  2,  3, -1, -5, -13,   4, -20,
-];
-
-pub const MG_QUEEN_TABLE: [i16; 64] = [
--28,   0,  29,  12,  59,  44,  43,  45,
--24, -39,  -5,   1, -16,  57,  28,  54,
--13, -17,   7,   8,  29,  56,  47,  57,
--27, -27, -16, -16,  -1,  17,  -2,   1,
--9, -26,  -9, -10,  -2,  -4,   3,  -3,
--14,   2, -11,  -2,  -5,   2,  14,   5,
--35,  -8,  11,   2,   8,  15,  -3,   1,
--1, -18,  -9,  10, -15, -25, -31, -50,
-];
-
-pub const EG_QUEEN_TABLE: [i16; 64] = [
--9,  22,  22,  27,  27,  19,  10,  20,
--17,  20,  32,  41,  58,  25,  30,   0,
--20,   6,   9,  49,  47,  35,  19,   9,
-3,  22,  24,  45,  57,  40,  57,  36,
--18,  28,  19,  47,  31,  34,  39,  23,
--16, -27,  15,   6,   9,  17,  10,   5,
--22, -23, -30, -16, -16, -23, -36, -32,
--33, -28, -22, -43,  -5, -32, -20, -41,
-];
-
-pub const MG_KING_TABLE: [i16; 64] = [
--65,  23,  16, -15, -56, -34,   2,  13,
-29,  -1, -20,  -7,  -8,  -4, -38, -29,
--9,  24,   2, -16, -20,   6,  22, -22,
--17, -20, -12, -27, -30, -25, -14, -36,
--49,  -1, -27, -39, -46, -44, -33, -51,
--14, -14, -22, -46, -44, -30, -15, -27,
-1,   7,  -8, -64, -43, -16,   9,   8,
--15,  36,  12, -54,   8, -28,  24,  14,
-];
-
-pub const EG_KING_TABLE: [i16; 64] = [
--74, -35, -18, -18, -11,  15,   4, -17,
--12,  17,  14,  17,  17,  38,  23,  11,
-10,  17,  23,  15,  20,  45,  44,  13,
--8,  22,  24,  27,  26,  33,  26,   3,
--18,  -4,  21,  24,  27,  23,   9, -11,
--19,  -3,  11,  21,  23,  16,   7,  -9,
--27, -11,   4,  13,  14,   4,  -5, -17,
--53, -34, -21, -11, -28, -14, -24, -43
-];
+/// Layout of `EVAL_PARAMS` (eval_params.rs, written by the tuner). The middlegame half:
+pub const MATERIAL_INDEX:       usize = 0;                         // 6 piece values
+pub const PST_INDEX:            usize = MATERIAL_INDEX + 6;        // 6 tables of 64, a8 first
+pub const SIMPLIFY_INDEX:       usize = PST_INDEX + 6 * 64;        // per piece traded off while ahead
+// pawn structure (engines/evaluate/pawns.rs)
+pub const DOUBLED_INDEX:        usize = SIMPLIFY_INDEX + 1;        // penalty per doubled pawn
+pub const ISOLATED_INDEX:       usize = DOUBLED_INDEX + 1;         // penalty per isolated pawn
+pub const BACKWARD_INDEX:       usize = ISOLATED_INDEX + 1;        // penalty per backward pawn
+pub const CONNECTED_INDEX:      usize = BACKWARD_INDEX + 1;        // 8 by relative rank, twice in a phalanx
+pub const PASSED_INDEX:         usize = CONNECTED_INDEX + 8;       // 8 by relative rank
+pub const FREE_PATH_INDEX:      usize = PASSED_INDEX + 8;          // 8 by relative rank
+pub const KING_PROXIMITY_INDEX: usize = FREE_PATH_INDEX + 8;       // 8 by relative rank, per square of king distance
+// king safety (engines/evaluate/king_safety.rs), all penalties
+pub const KING_ATTACK_INDEX:    usize = KING_PROXIMITY_INDEX + 8;  // N, B, R, Q per attacked king-zone square
+pub const SHIELD_INDEX:         usize = KING_ATTACK_INDEX + 4;     // shield pawn advanced, missing
+pub const KING_FILE_INDEX:      usize = SHIELD_INDEX + 2;          // semi-open, open file near the king
+// mobility and piece activity (engines/evaluate/mobility.rs)
+pub const MOBILITY_INDEX:       usize = KING_FILE_INDEX + 2;       // N, B, R, Q per safe square
+pub const BISHOP_PAIR_INDEX:    usize = MOBILITY_INDEX + 4;
+pub const ROOK_FILE_INDEX:      usize = BISHOP_PAIR_INDEX + 1;     // open, semi-open file
+pub const ROOK_SEVENTH_INDEX:   usize = ROOK_FILE_INDEX + 2;
+pub const OUTPOST_INDEX:        usize = ROOK_SEVENTH_INDEX + 1;
+pub const TRAPPED_INDEX:        usize = OUTPOST_INDEX + 1;         // penalty for a trapped bishop, rook
+/// The endgame half repeats the middlegame layout this far on.
+pub const EG_PARAMS:            usize = TRAPPED_INDEX + 2;
+pub const NUM_EVAL_PARAMS:      usize = 2 * EG_PARAMS;
+
+const fn params<const N: usize>(index: usize) -> [i16; N] {
+    let mut values = [0; N];
+    let mut i = 0;
+    while i < N {
+        values[i] = EVAL_PARAMS[index + i];
+        i += 1;
+    }
+    values
+}
+
+/// Where the eval terms that are not kept incrementally go, as counts of the middlegame
+/// parameter at `index` (White minus Black): `Tapered` weighs them with `EVAL_PARAMS`, the
+/// tuner keeps the counts themselves.
+pub(crate) trait Terms {
+    fn add(&mut self, index: usize, count: i32);
+    /// Like `add`, with the count in hundredths.
+    fn add_percent(&mut self, index: usize, percent: i32);
+}
+
+/// Middlegame and endgame sums of eval terms, White's point of view.
+#[derive(Clone, Copy, Default, Debug)]
+pub(crate) struct Tapered {
+    pub(crate) mg: i32,
+    pub(crate) eg: i32,
+}
 
+impl Terms for Tapered {
+    #[inline(always)]
+    fn add(&mut self, index: usize, count: i32) {
+        self.mg += count * EVAL_PARAMS[index] as i32;
+        self.eg += count * EVAL_PARAMS[EG_PARAMS + index] as i32;
+    }
+
+    #[inline(always)]
+    fn add_percent(&mut self, index: usize, percent: i32) {
+        self.mg += percent * EVAL_PARAMS[index] as i32 / 100;
+        self.eg += percent * EVAL_PARAMS[EG_PARAMS + index] as i32 / 100;
+    }
+}
+
+const fn pst(phase: usize, piece: Piece) -> [i16; 64] {
+    params(phase + PST_INDEX + piece as usize * 64)
+}
+
+// Pawn, Knight, …, King PSTs (white’s view)
+pub const MG_PAWN_TABLE:   [i16; 64] = pst(0, Piece::Pawn);
+pub const EG_PAWN_TABLE:   [i16; 64] = pst(EG_PARAMS, Piece::Pawn);
+pub const MG_KNIGHT_TABLE: [i16; 64] = pst(0, Piece::Knight);
+pub const EG_KNIGHT_TABLE: [i16; 64] = pst(EG_PARAMS, Piece::Knight);
+pub const MG_BISHOP_TABLE: [i16; 64] = pst(0, Piece::Bishop);
+pub const EG_BISHOP_TABLE: [i16; 64] = pst(EG_PARAMS, Piece::Bishop);
+pub const MG_ROOK_TABLE:   [i16; 64] = pst(0, Piece::Rook);
+pub const EG_ROOK_TABLE:   [i16; 64] = pst(EG_PARAMS, Piece::Rook);
+pub const MG_QUEEN_TABLE:  [i16; 64] = pst(0, Piece::Queen);
+pub const EG_QUEEN_TABLE:  [i16; 64] = pst(EG_PARAMS, Piece::Queen);
+pub const MG_KING_TABLE:   [i16; 64] = pst(0, Piece::King);
+pub const EG_KING_TABLE:   [i16; 64] = pst(EG_PARAMS, Piece::King);
 
 pub fn build_eval(pos: &Position) -> EvalCache {
     let mut cache = EvalCache::default();
@@ -169,8 +121,8 @@ pub fn build_eval(pos: &Position) -> EvalCache {
 
 
 
-pub const MG_VALUE: [i16; 6] = [  82, 337, 365, 477, 1025,   0];
-pub const EG_VALUE: [i16; 6] = [  94, 281, 297, 512,  936,   0];
+pub const MG_VALUE: [i16; 6] = params(MATERIAL_INDEX);
+pub const EG_VALUE: [i16; 6] = params(EG_PARAMS + MATERIAL_INDEX);
 
 pub const PST_MG: [&[i16; 64]; 6] = [&MG_PAWN_TABLE, &MG_KNIGHT_TABLE, &MG_BISHOP_TABLE,
     &MG_ROOK_TABLE, &MG_QUEEN_TABLE, &MG_KING_TABLE];
@@ -0,0 +1,246 @@
+use std::fmt;
+use crate::color::Color;
+
+/// The six whitespace separated fields of a FEN string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FenField {
+    Board,
+    SideToMove,
+    Castling,
+    EnPassant,
+    HalfMove,
+    FullMove,
+}
+
+/// Why a FEN string was rejected. Every variant carries the byte offset into the FEN
+/// string where the problem starts; [`FenError::field`] names the field it belongs to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FenError {
+    MissingField    { field: FenField, offset: usize },
+    TrailingInput   { offset: usize },
+    RankCount       { offset: usize, ranks: usize },
+    RankLength      { offset: usize, rank: u8 },
+    InvalidPiece    { offset: usize, found: char },
+    TooManyPieces   { offset: usize, piece: char },
+    PawnOnBackRank  { offset: usize },
+    KingCount       { offset: usize, color: Color, count: usize },
+    OpponentInCheck { offset: usize },
+    SideToMove      { offset: usize },
+    Castling        { offset: usize },
+    EnPassant       { offset: usize },
+    HalfMove        { offset: usize },
+    FullMove        { offset: usize },
+}
+
+impl FenError {
+    pub fn field(&self) -> FenField {
+        match self {
+            FenError::MissingField { field, .. } => *field,
+            FenError::TrailingInput { .. }
+            | FenError::FullMove { .. } => FenField::FullMove,
+            FenError::SideToMove { .. } => FenField::SideToMove,
+            FenError::Castling   { .. } => FenField::Castling,
+            FenError::EnPassant  { .. } => FenField::EnPassant,
+            FenError::HalfMove   { .. } => FenField::HalfMove,
+            _ => FenField::Board,
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        match *self {
+            FenError::MissingField    { offset, .. }
+            | FenError::TrailingInput   { offset }
+            | FenError::RankCount       { offset, .. }
+            | FenError::RankLength      { offset, .. }
+            | FenError::InvalidPiece    { offset, .. }
+            | FenError::TooManyPieces   { offset, .. }
+            | FenError::PawnOnBackRank  { offset }
+            | FenError::KingCount       { offset, .. }
+            | FenError::OpponentInCheck { offset }
+            | FenError::SideToMove      { offset }
+            | FenError::Castling        { offset }
+            | FenError::EnPassant       { offset }
+            | FenError::HalfMove        { offset }
+            | FenError::FullMove        { offset } => offset,
+        }
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::MissingField { field, .. } => write!(f, "missing {field:?} field")?,
+            FenError::TrailingInput { .. }       => write!(f, "unexpected input after the full-move number")?,
+            FenError::RankCount { ranks, .. }    => write!(f, "expected 8 ranks, found {ranks}")?,
+            FenError::RankLength { rank, .. }    => write!(f, "rank {rank} does not describe exactly 8 files")?,
+            FenError::InvalidPiece { found, .. } => write!(f, "invalid piece character '{found}'")?,
+            FenError::TooManyPieces { piece, .. } => write!(f, "too many '{piece}' pieces")?,
+            FenError::PawnOnBackRank { .. }      => write!(f, "pawn on the first or eighth rank")?,
+            FenError::KingCount { color, count, .. } => write!(f, "{color:?} has {count} kings, expected 1")?,
+            FenError::OpponentInCheck { .. }     => write!(f, "the side not to move is in check")?,
+            FenError::SideToMove { .. }          => write!(f, "side to move must be 'w' or 'b'")?,
+            FenError::Castling { .. }            => write!(f, "castling rights must be '-' or a subset of 'KQkq' with king and rook at home")?,
+            FenError::EnPassant { .. }           => write!(f, "invalid en passant square, or no pawn that just double-pushed past it")?,
+            FenError::HalfMove { .. }            => write!(f, "invalid half-move clock")?,
+            FenError::FullMove { .. }            => write!(f, "invalid full-move number")?,
+        }
+        write!(f, " (at offset {})", self.offset())
+    }
+}
+
+impl std::error::Error for FenError {}
+
+
+// Same bound as the piece lists in `Position`.
+const MAX_PIECES_PER_KIND: usize = 10;
+
+/// Checks everything about a FEN string that can be seen without building the position.
+/// The half-move and full-move fields may be left out; all other fields are required.
+pub(crate) fn validate(fen: &str) -> Result<(), FenError> {
+    let fields = split_fields(fen);
+    let field = |i: usize, kind: FenField| {
+        fields.get(i).copied().ok_or(FenError::MissingField { field: kind, offset: fen.len() })
+    };
+
+    let (offset, board) = field(0, FenField::Board)?;
+    let board = validate_board(board, offset)?;
+
+    let (offset, side) = field(1, FenField::SideToMove)?;
+    let white_to_move = match side {
+        "w" => true,
+        "b" => false,
+        _   => return Err(FenError::SideToMove { offset }),
+    };
+
+    let (offset, castling) = field(2, FenField::Castling)?;
+    if castling != "-" {
+        let mut seen = String::new();
+        for (i, ch) in castling.char_indices() {
+            if !"KQkq".contains(ch) || seen.contains(ch) {
+                return Err(FenError::Castling { offset: offset + i });
+            }
+            seen.push(ch);
+        }
+        // every right needs its king and rook still on their starting squares
+        for (i, ch) in castling.char_indices() {
+            let (king, rook, rank, rook_file) = match ch {
+                'K' => ('K', 'R', 1, 7),
+                'Q' => ('K', 'R', 1, 0),
+                'k' => ('k', 'r', 8, 7),
+                _   => ('k', 'r', 8, 0),
+            };
+            if board.at(4, rank) != Some(king) || board.at(rook_file, rank) != Some(rook) {
+                return Err(FenError::Castling { offset: offset + i });
+            }
+        }
+    }
+
+    let (offset, ep) = field(3, FenField::EnPassant)?;
+    if ep != "-" {
+        let bytes = ep.as_bytes();
+        // the en passant square sits behind a pawn of the side that just moved
+        let rank = if white_to_move { b'6' } else { b'3' };
+        if bytes.len() != 2 || !(b'a'..=b'h').contains(&bytes[0]) || bytes[1] != rank {
+            return Err(FenError::EnPassant { offset });
+        }
+        // and that pawn has just come from two squares further back
+        let file = bytes[0] - b'a';
+        let (pawn, pawn_rank, from_rank, ep_rank) = if white_to_move { ('p', 5, 7, 6) } else { ('P', 4, 2, 3) };
+        if board.at(file, pawn_rank) != Some(pawn) || board.at(file, ep_rank).is_some() || board.at(file, from_rank).is_some() {
+            return Err(FenError::EnPassant { offset });
+        }
+    }
+
+    if let Some(&(offset, half_move)) = fields.get(4)
+        && half_move.parse::<u16>().is_err()
+    {
+        return Err(FenError::HalfMove { offset });
+    }
+    if let Some(&(offset, full_move)) = fields.get(5)
+        && full_move.parse::<u16>().is_err()
+    {
+        return Err(FenError::FullMove { offset });
+    }
+    if let Some(&(offset, _)) = fields.get(6) {
+        return Err(FenError::TrailingInput { offset });
+    }
+    Ok(())
+}
+
+fn split_fields(fen: &str) -> Vec<(usize, &str)> {
+    let mut fields = Vec::new();
+    let mut start = None;
+    for (i, ch) in fen.char_indices() {
+        match (ch.is_whitespace(), start) {
+            (true, Some(s)) => {
+                fields.push((s, &fen[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        fields.push((s, &fen[s..]));
+    }
+    fields
+}
+
+/// The pieces of a board field, a8 first.
+struct Board([Option<char>; 64]);
+
+impl Board {
+    /// The piece on the square at `file` (0 for a) and `rank` (1 to 8).
+    fn at(&self, file: u8, rank: u8) -> Option<char> {
+        self.0[(8 - rank as usize) * 8 + file as usize]
+    }
+}
+
+fn validate_board(board: &str, offset: usize) -> Result<Board, FenError> {
+    let ranks = board.split('/').count();
+    if ranks != 8 {
+        return Err(FenError::RankCount { offset, ranks });
+    }
+
+    let mut counts = [0usize; 128];
+    let mut squares = [None; 64];
+    let mut rank_offset = offset;
+    for (row, rank) in board.split('/').enumerate() {
+        let mut files = 0;
+        for (i, ch) in rank.char_indices() {
+            let at = rank_offset + i;
+            if let Some(empties) = ch.to_digit(10).filter(|d| (1..=8).contains(d)) {
+                files += empties as usize;
+            } else if "pnbrqkPNBRQK".contains(ch) {
+                if files < 8 {
+                    squares[row * 8 + files] = Some(ch);
+                }
+                files += 1;
+                counts[ch as usize] += 1;
+                if counts[ch as usize] > MAX_PIECES_PER_KIND {
+                    return Err(FenError::TooManyPieces { offset: at, piece: ch });
+                }
+                if ch.eq_ignore_ascii_case(&'p') && (row == 0 || row == 7) {
+                    return Err(FenError::PawnOnBackRank { offset: at });
+                }
+            } else {
+                return Err(FenError::InvalidPiece { offset: at, found: ch });
+            }
+            if files > 8 {
+                return Err(FenError::RankLength { offset: rank_offset, rank: 8 - row as u8 });
+            }
+        }
+        if files != 8 {
+            return Err(FenError::RankLength { offset: rank_offset, rank: 8 - row as u8 });
+        }
+        rank_offset += rank.len() + 1;
+    }
+
+    for (king, color) in [('K', Color::White), ('k', Color::Black)] {
+        let count = counts[king as usize];
+        if count != 1 {
+            return Err(FenError::KingCount { offset, color, count });
+        }
+    }
+    Ok(Board(squares))
+}
@@ -1,14 +1,19 @@
 use crate::attacks::movegen::all_moves;
 use crate::color::Color;
 use crate::color::Color::White;
-use crate::engines::engine_manager::{Engine, NUMBER_OF_EVAL_ALGORITHMS};
+use crate::engines::engine_manager::{Engine, SearchLimits, NUMBER_OF_EVAL_ALGORITHMS};
+use crate::engines::score::Score;
+use crate::engines::time_manager::Clock;
 use crate::gui::{GuiState, UiEvent};
 use crate::mov::{Move, MoveList};
 use crate::position::{Position, Status, NO_SQ};
 use crate::undo::UndoStack;
 use macroquad::prelude::{get_time, is_key_pressed, is_mouse_button_pressed, mouse_position, next_frame, KeyCode, MouseButton};
 use std::cmp::PartialEq;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{Receiver, Sender};
+use std::time::Instant;
 
 struct MoveAnimation {
     from: u8,
@@ -32,7 +37,7 @@ enum EvalRequest {
 
 #[derive(Clone, Copy)]
 struct EvalUpdate {
-    eval: i16,
+    eval: Score,
     depth: u8,
     best: Move,
     nodes: u64,
@@ -42,6 +47,8 @@ struct EvalUpdate {
 pub struct GameController {
     white_engine: Engine,
     black_engine: Engine,
+    white_clock: Clock,
+    black_clock: Clock,
     position: Position,
     gui: GuiState,
     selected_moves: MoveList,
@@ -50,12 +57,13 @@ pub struct GameController {
     game_status: Status,
 
     last_depth: u8,
-    last_eval: i16,
+    last_eval: Score,
     last_move: Move,
 
-    eval_tx: Sender<EvalRequest>,
+    eval_tx: Arc<Mutex<Sender<EvalRequest>>>,  // also held while the stop flag is raised
+    eval_stop: Arc<AtomicBool>,
     eval_rx: Receiver<EvalUpdate>,
-    live_eval: i16,
+    live_eval: Score,
     live_eval_depth: u8,
 
     move_anim: Option<MoveAnimation>,
@@ -73,6 +81,10 @@ pub enum GameMode {
 const PLAYER_COLOR: Color = White;
 const PLAYERS_ONLY: bool = false;
 
+// engines play on a 5+3 clock
+const ENGINE_CLOCK_MS: u64 = 5 * 60 * 1000;
+const ENGINE_INC_MS:   u64 = 3000;
+
 
 impl GameController {
 
@@ -82,21 +94,23 @@ impl GameController {
         let position = Position::start();
         //let position = Position::load_position_from_fen("8/8/8/8/3k4/8/6q1/4K3 b - - 0 0");
         let gui = GuiState::new(game_mode == GameMode::PlayerBlack, game_mode.clone(), white_engine.name(), black_engine.name()).await;
-        let (eval_tx, eval_rx) = spawn_eval_worker();
+        let (eval_tx, eval_rx, eval_stop) = spawn_eval_worker();
 
         let me = Self {
             white_engine, black_engine, position, gui,
+            white_clock: Clock::new(ENGINE_CLOCK_MS, ENGINE_INC_MS),
+            black_clock: Clock::new(ENGINE_CLOCK_MS, ENGINE_INC_MS),
             selected_moves: MoveList::new(),
             selected_square: NO_SQ,
             game_mode,
             game_status: Status::Ongoing,
-            last_depth: 0, last_eval: 0, last_move: Move::null(),
-            eval_tx, eval_rx, live_eval: 0, live_eval_depth: 0,
+            last_depth: 0, last_eval: Score::default(), last_move: Move::null(),
+            eval_tx, eval_rx, eval_stop, live_eval: Score::default(), live_eval_depth: 0,
             move_anim: None, anim_ms: 400.0,
         };
 
         // kick the worker with the initial position
-        let _ = me.eval_tx.send(EvalRequest::NewPosition(me.position.clone()));
+        me.push_eval_position();
         me
     }
 
@@ -166,11 +180,15 @@ impl GameController {
         self.push_eval_position();
         self.selected_moves = MoveList::new();
         self.selected_square = NO_SQ;
-        self.last_eval = 0;
+        self.last_eval = Score::default();
         self.last_depth = 0;
         self.last_move = Move::null();
         self.position = Position::start();
-        self.live_eval = 0;
+        self.white_engine.clear_hash();
+        self.black_engine.clear_hash();
+        self.white_clock = Clock::new(ENGINE_CLOCK_MS, ENGINE_INC_MS);
+        self.black_clock = Clock::new(ENGINE_CLOCK_MS, ENGINE_INC_MS);
+        self.live_eval = Score::default();
         self.live_eval_depth = 0;
         self.push_eval_position();
     }
@@ -213,7 +231,11 @@ impl GameController {
     }
 
     fn push_eval_position(&self) {
-        let _ = self.eval_tx.send(EvalRequest::NewPosition(self.position.clone()));
+        // stop the running search first: raised after sending, the flag could abort the
+        // search of this very position
+        let eval_tx = self.eval_tx.lock().unwrap();
+        self.eval_stop.store(true, Ordering::Relaxed);
+        let _ = eval_tx.send(EvalRequest::NewPosition(self.position.clone()));
     }
 
 
@@ -232,7 +254,10 @@ impl GameController {
         self.render().await;
         next_frame().await;
 
+        self.white_engine.set_clock(Some(self.white_clock));
+        let started = Instant::now();
         let (mov, depth, eval) = self.white_engine.pick_and_stats(&mut self.position);
+        self.white_clock.punch(started.elapsed().as_millis() as u64);
 
         self.position.do_move(mov);
         self.push_eval_position();
@@ -252,7 +277,10 @@ impl GameController {
         self.render().await;
         next_frame().await;
 
+        self.black_engine.set_clock(Some(self.black_clock));
+        let started = Instant::now();
         let (mov, depth, eval) = self.black_engine.pick_and_stats(&mut self.position);
+        self.black_clock.punch(started.elapsed().as_millis() as u64);
 
         self.position.do_move(mov);
         self.push_eval_position();
@@ -345,85 +373,49 @@ impl GameController {
     }
 }
 
-fn spawn_eval_worker() -> (Sender<EvalRequest>, Receiver<EvalUpdate>) {
+/// Background analysis of the current position: searches without a time limit and reports
+/// every finished iteration. A new position raises the stop flag, which aborts the running
+/// search so the worker can move on straight away.
+fn spawn_eval_worker() -> (Arc<Mutex<Sender<EvalRequest>>>, Receiver<EvalUpdate>, Arc<AtomicBool>) {
     use std::sync::mpsc::{self};
     use std::thread;
 
     let (tx_req, rx_req) = mpsc::channel::<EvalRequest>();
     let (tx_upd, rx_upd) = mpsc::channel::<EvalUpdate>();
+    let tx_req = Arc::new(Mutex::new(tx_req));
+    // weak, so that the channel still closes when the controller goes away
+    let requests = Arc::downgrade(&tx_req);
 
-    thread::spawn(move || {
-        const BASE_MS: u64 = 200;   // responsive when positions change
-        const MAX_MS:  u64 = 3000;  // cap so it still reacts within ~3s
-        let mut slice_ms = BASE_MS;
-
-        // strongest search, best eval, initial slice
-        let mut engine = Engine::new(29, 2, slice_ms);
+    // strongest search, best eval, analysis until told otherwise
+    let mut engine = Engine::new(29, 2, 0);
+    engine.set_limits(SearchLimits { infinite: true, ..Default::default() });
+    let stop = engine.stop_flag();
 
-        let mut current: Option<Position> = None;
-        let mut last_depth: u8 = 0;
-        let mut stale: u32 = 0;
-
-        loop {
-            if current.is_none() {
-                match rx_req.recv() {
-                    Ok(EvalRequest::NewPosition(p)) => {
-                        current = Some(p);
-                        last_depth = 0;
-                        slice_ms = BASE_MS;
-                        stale = 0;
-                        engine.set_time_limit(slice_ms);
-                    }
-                    Ok(EvalRequest::Quit) | Err(_) => break,
+    thread::spawn(move || {
+        while let Ok(mut request) = rx_req.recv() {
+            // the sender lock keeps a new position from raising the flag in between: it
+            // is either among the ones drained here or raises the flag after it is lowered
+            {
+                let Some(requests) = requests.upgrade() else { break };
+                let _sending = requests.lock().unwrap();
+                engine.stop_flag().store(false, Ordering::Relaxed);
+                while let Ok(newer) = rx_req.try_recv() {
+                    request = newer;
                 }
             }
-
-            // run one slice
-            engine.set_time_limit(slice_ms);
-            let pos_ref = current.as_ref().unwrap();
-            let stm_is_white = pos_ref.side_to_move().is_white();
-
-            let mut pos = pos_ref.clone();
-            let (best, depth, eval_stm) = engine.pick_and_stats(&mut pos);
+            let EvalRequest::NewPosition(mut pos) = request else { break };
 
             // flip to White perspective for the UI
-            let eval_white = if stm_is_white { eval_stm } else { -eval_stm };
-
-            let _ = tx_upd.send(EvalUpdate { eval: eval_white, depth, best, nodes: engine.total_nodes() });
-
-            // adapt the slice length
-            if depth > last_depth {
-                last_depth = depth;
-                stale = 0;
-                // decay toward base to stay snappy
-                if slice_ms > BASE_MS {
-                    slice_ms = (slice_ms as f32 * 0.6).max(BASE_MS as f32) as u64;
-                }
-            } else {
-                stale += 1;
-                if stale >= 3 && slice_ms < MAX_MS {
-                    slice_ms = (slice_ms * 2).min(MAX_MS);
-                    stale = 0;
-                }
-            }
-
-            // drain any new positions, keep only the latest
-            let mut latest: Option<Position> = None;
-            while let Ok(msg) = rx_req.try_recv() {
-                match msg {
-                    EvalRequest::NewPosition(p) => latest = Some(p),
-                    EvalRequest::Quit => return,
-                }
-            }
-            if let Some(p) = latest {
-                current = Some(p);
-                last_depth = 0;
-                slice_ms = BASE_MS;
-                stale = 0;
-                engine.set_time_limit(slice_ms);
-            }
+            let stm_is_white = pos.side_to_move().is_white();
+            let tx_upd = tx_upd.clone();
+            engine.set_info_callback(move |info| {
+                let eval = if stm_is_white { info.score } else { -info.score };
+                let best = info.pv.first().copied().unwrap_or(Move::null());
+                let _ = tx_upd.send(EvalUpdate { eval, depth: info.depth, best, nodes: info.nodes });
+            });
+            engine.pick_and_stats(&mut pos);
         }
     });
 
-    (tx_req, rx_upd)
+    (tx_req, rx_upd, stop)
 }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use macroquad::prelude::*;
 use macroquad::ui::{root_ui, widgets, Skin};
 use crate::mov::{Move, MoveList};
+use crate::engines::score::Score;
 use crate::color::Color::{Black, White};
 use crate::game_controller::GameMode;
 use crate::position::{Position, Status};
@@ -196,7 +197,7 @@ impl GuiState {
         }
     }
 
-    pub fn draw_eval_bar(&self, eval_cp: i16) {
+    pub fn draw_eval_bar(&self, eval: Score) {
         let bar_w = 20.0;
         let bar_h = BOARD_PIXELS;
         let x = self.x_offset + BOARD_PIXELS + 40.0; // right of the board
@@ -208,7 +209,10 @@ impl GuiState {
 
         // normalize eval to 0..1 with a tanh squish
         let scale = 600.0; // tweak: 100 = 1 pawn; 600 gives nice curve
-        let p = 0.5 + 0.5 * ((eval_cp as f32) / scale).tanh();
+        let p = match eval {
+            Score::Cp(cp)  => 0.5 + 0.5 * ((cp as f32) / scale).tanh(),
+            Score::Mate(n) => if n > 0 { 1.0 } else { 0.0 },
+        };
         let white_h = bar_h * p;
 
         if !self.flipped {
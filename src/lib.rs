@@ -1,5 +1,8 @@
 pub mod game_controller;
 pub mod position;
+pub mod fen;
+pub mod notation;
+pub mod pgn;
 mod bitboards;
 pub mod color;
 mod castling_rights;
@@ -13,6 +16,9 @@ mod undo;
 
 pub mod engines;
 pub mod simulator;
+pub mod tuner;
+pub mod uci;
 pub mod tables;
 mod eval;
+mod eval_params;
 
@@ -1,6 +1,7 @@
 use macroquad::prelude::Conf;
 
 use chess::game_controller::{GameController, GameMode};
+use chess::engines::bitbase;
 use chess::mov::init_sounds;
 
 fn window_conf() -> Conf {
@@ -16,6 +17,8 @@ async fn main() {
 
     //battle_against_other_eval_algos(3, 3).await;
     //battle_against_other_search_algos(4, 3, 5, 100);
+    // build the endgame bitbases while the player makes the first moves
+    std::thread::spawn(bitbase::init);
     init_sounds().await;
     let mut controller = GameController::new(GameMode::PlayerWhite).await;
     controller.run().await;
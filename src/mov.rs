@@ -82,6 +82,10 @@ impl Move {
         Move(mov)
     }
     #[inline(always)]
+    pub fn raw(self) -> u16 {
+        self.0
+    }
+    #[inline(always)]
     pub fn encode(from: u8, to: u8, flags: u16) -> Move {
         Move(
             (from as u16)   << FROM_SHIFT  |
@@ -0,0 +1,236 @@
+use std::fmt;
+use crate::attacks::movegen::all_moves;
+use crate::mov::Move;
+use crate::piece::Piece;
+use crate::position::Position;
+
+/// Why a move written as text could not be turned into a legal `Move`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveParseError {
+    /// The text is not a well-formed move.
+    Syntax(String),
+    /// The text is well-formed but no legal move matches it.
+    Illegal(String),
+    /// More than one legal move matches (SAN without enough disambiguation).
+    Ambiguous(String),
+}
+
+impl fmt::Display for MoveParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveParseError::Syntax(s)    => write!(f, "malformed move '{s}'"),
+            MoveParseError::Illegal(s)   => write!(f, "illegal move '{s}'"),
+            MoveParseError::Ambiguous(s) => write!(f, "ambiguous move '{s}'"),
+        }
+    }
+}
+
+impl std::error::Error for MoveParseError {}
+
+
+impl Position {
+    /// Writes a legal move in Standard Algebraic Notation, e.g. `Nbd7`, `exd6`, `e8=Q+`, `O-O#`.
+    pub fn move_to_san(&self, mv: Move) -> String {
+        let mut san = if mv.is_king_castle() {
+            "O-O".to_string()
+        } else if mv.is_queen_castle() {
+            "O-O-O".to_string()
+        } else {
+            let piece   = self.piece_at_sq(mv.from());
+            let capture = mv.is_capture() || mv.is_en_passant();
+            let mut san = String::new();
+
+            if piece == Piece::Pawn {
+                if capture {
+                    san.push(file_char(mv.from()));
+                }
+            } else {
+                san.push(piece.piece_initial().to_ascii_uppercase());
+                san += &self.disambiguation(mv, piece);
+            }
+            if capture {
+                san.push('x');
+            }
+            san.push(file_char(mv.to()));
+            san.push(rank_char(mv.to()));
+            if mv.is_promotion() {
+                san.push('=');
+                san.push(mv.promotion_piece().piece_initial().to_ascii_uppercase());
+            }
+            san
+        };
+
+        let mut after = self.clone();
+        if after.undo_stack.is_near_full() {
+            after.undo_stack.make_space();
+        }
+        after.do_move(mv);
+        if after.in_check() {
+            san.push(if all_moves(&after).is_empty() { '#' } else { '+' });
+        }
+        san
+    }
+
+    /// The file, rank or square needed to tell `mv` apart from other legal moves of the same
+    /// piece type to the same square.
+    fn disambiguation(&self, mv: Move, piece: Piece) -> String {
+        let rivals: Vec<u8> = all_moves(self)
+            .iter()
+            .filter(|other| other.to() == mv.to() && other.from() != mv.from())
+            .filter(|other| self.piece_at_sq(other.from()) == piece)
+            .map(|other| other.from())
+            .collect();
+
+        if rivals.is_empty() {
+            String::new()
+        } else if rivals.iter().all(|&sq| sq % 8 != mv.from() % 8) {
+            file_char(mv.from()).to_string()
+        } else if rivals.iter().all(|&sq| sq / 8 != mv.from() / 8) {
+            rank_char(mv.from()).to_string()
+        } else {
+            format!("{}{}", file_char(mv.from()), rank_char(mv.from()))
+        }
+    }
+
+    /// Resolves a move in long algebraic (UCI) notation, e.g. `e2e4` or `e7e8q`, against the
+    /// legal moves of this position, so the flag (double push, en passant, castle, promotion
+    /// capture) comes from the move generator.
+    pub fn parse_uci_move(&self, uci: &str) -> Result<Move, MoveParseError> {
+        let syntax = || MoveParseError::Syntax(uci.to_string());
+        let chars: Vec<char> = uci.trim().chars().collect();
+        if chars.len() != 4 && chars.len() != 5 {
+            return Err(syntax());
+        }
+
+        let from = square_from_chars(chars[0], chars[1]).ok_or_else(syntax)?;
+        let to   = square_from_chars(chars[2], chars[3]).ok_or_else(syntax)?;
+        let promotion = match chars.get(4) {
+            Some(&ch) => match piece_from_letter(ch.to_ascii_uppercase()) {
+                Some(Piece::King) | None => return Err(syntax()),
+                promo => promo,
+            },
+            None => None,
+        };
+
+        all_moves(self)
+            .iter()
+            .find(|mv| {
+                mv.from() == from
+                    && mv.to() == to
+                    && match promotion {
+                        Some(promo) => mv.is_promotion() && mv.promotion_piece() == promo,
+                        None        => !mv.is_promotion(),
+                    }
+            })
+            .ok_or_else(|| MoveParseError::Illegal(uci.to_string()))
+    }
+
+    /// Resolves a SAN move against the legal moves of this position. Check, mate and
+    /// annotation suffixes (`+`, `#`, `!`, `?`) are ignored, and `0-0` is accepted for `O-O`.
+    pub fn parse_san(&self, san: &str) -> Result<Move, MoveParseError> {
+        let text = san.trim().trim_end_matches(['+', '#', '!', '?']);
+        let legal = all_moves(self);
+
+        match text {
+            "O-O" | "0-0" => {
+                return legal.iter().find(|mv| mv.is_king_castle())
+                    .ok_or_else(|| MoveParseError::Illegal(san.to_string()));
+            }
+            "O-O-O" | "0-0-0" => {
+                return legal.iter().find(|mv| mv.is_queen_castle())
+                    .ok_or_else(|| MoveParseError::Illegal(san.to_string()));
+            }
+            _ => {}
+        }
+
+        let syntax = || MoveParseError::Syntax(san.to_string());
+        let mut chars: Vec<char> = text.chars().collect();
+
+        // leading piece letter (pawn moves have none)
+        let piece = match chars.first().copied().and_then(piece_from_letter) {
+            Some(piece) => {
+                chars.remove(0);
+                piece
+            }
+            None => Piece::Pawn,
+        };
+
+        // trailing promotion, "=Q" or a bare "Q"
+        let mut promotion = None;
+        if let Some(&last) = chars.last() && let Some(promo) = piece_from_letter(last) {
+            if promo == Piece::King {
+                return Err(syntax());
+            }
+            chars.pop();
+            if chars.last() == Some(&'=') {
+                chars.pop();
+            }
+            promotion = Some(promo);
+        }
+
+        // destination square
+        if chars.len() < 2 {
+            return Err(syntax());
+        }
+        let to = square_from_chars(chars[chars.len() - 2], chars[chars.len() - 1]).ok_or_else(syntax)?;
+        chars.truncate(chars.len() - 2);
+
+        // optional capture marker and origin file / rank / square
+        if chars.last() == Some(&'x') {
+            chars.pop();
+        }
+        let mut from_file = None;
+        let mut from_rank = None;
+        for ch in chars {
+            match ch {
+                'a'..='h' if from_file.is_none() && from_rank.is_none() => from_file = Some(ch as u8 - b'a'),
+                '1'..='8' if from_rank.is_none() => from_rank = Some(ch as u8 - b'1'),
+                _ => return Err(syntax()),
+            }
+        }
+
+        let mut matches = legal.iter().filter(|mv| {
+            mv.to() == to
+                && self.piece_at_sq(mv.from()) == piece
+                && from_file.is_none_or(|file| mv.from() % 8 == file)
+                && from_rank.is_none_or(|rank| mv.from() / 8 == rank)
+                && match promotion {
+                    Some(promo) => mv.is_promotion() && mv.promotion_piece() == promo,
+                    None        => !mv.is_promotion(),
+                }
+        });
+
+        match (matches.next(), matches.next()) {
+            (Some(mv), None) => Ok(mv),
+            (None, _)        => Err(MoveParseError::Illegal(san.to_string())),
+            (Some(_), Some(_)) => Err(MoveParseError::Ambiguous(san.to_string())),
+        }
+    }
+}
+
+fn file_char(sq: u8) -> char {
+    (b'a' + sq % 8) as char
+}
+
+fn rank_char(sq: u8) -> char {
+    (b'1' + sq / 8) as char
+}
+
+fn square_from_chars(file: char, rank: char) -> Option<u8> {
+    if ('a'..='h').contains(&file) && ('1'..='8').contains(&rank) {
+        Some((rank as u8 - b'1') * 8 + (file as u8 - b'a'))
+    } else {
+        None
+    }
+}
+
+fn piece_from_letter(ch: char) -> Option<Piece> {
+    match ch {
+        'N' => Some(Piece::Knight),
+        'B' => Some(Piece::Bishop),
+        'R' => Some(Piece::Rook),
+        'Q' => Some(Piece::Queen),
+        'K' => Some(Piece::King),
+        _   => None,
+    }
+}
@@ -0,0 +1,356 @@
+use std::fmt;
+use crate::color::Color;
+use crate::fen::FenError;
+use crate::mov::Move;
+use crate::notation::MoveParseError;
+use crate::position::{Position, Status};
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const SEVEN_TAG_ROSTER: [(&str, &str); 6] = [
+    ("Event", "?"),
+    ("Site",  "?"),
+    ("Date",  "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+];
+const LINE_WIDTH: usize = 80;
+
+
+/// Engine output attached to a move, written as a `{+0.35/12}` comment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MoveEval {
+    pub score: i16,  // centipawns, from the point of view of the side that moved
+    pub depth: u8,
+}
+
+/// One game: its tags, start position, main line and result. Variations are not kept.
+#[derive(Clone, Debug)]
+pub struct Game {
+    tags:       Vec<(String, String)>,
+    pub start:  Position,
+    pub moves:  Vec<Move>,
+    pub evals:  Vec<Option<MoveEval>>,  // parallel to `moves`
+    pub result: Status,                 // `Ongoing` is written as `*`
+}
+
+impl Game {
+    pub fn new(start: Position) -> Game {
+        Game { tags: Vec::new(), start, moves: Vec::new(), evals: Vec::new(), result: Status::Ongoing }
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+
+    pub fn push(&mut self, mv: Move, eval: Option<MoveEval>) {
+        self.moves.push(mv);
+        self.evals.push(eval);
+    }
+
+    /// The position after the last move of the main line.
+    pub fn end_position(&self) -> Position {
+        let mut pos = self.start.clone();
+        for &mv in &self.moves {
+            play(&mut pos, mv);
+        }
+        pos
+    }
+
+    pub fn to_pgn(&self) -> String {
+        let mut out = String::new();
+
+        // seven tag roster first, in its fixed order
+        for (name, default) in SEVEN_TAG_ROSTER {
+            out += &tag_line(name, self.tag(name).unwrap_or(default));
+        }
+        out += &tag_line("Result", result_str(self.result));
+
+        let fen = self.start.to_fen();
+        if fen != START_FEN {
+            out += &tag_line("SetUp", "1");
+            out += &tag_line("FEN", &fen);
+        }
+        for (name, value) in &self.tags {
+            let reserved = SEVEN_TAG_ROSTER.iter().any(|(n, _)| n == name)
+                || matches!(name.as_str(), "Result" | "SetUp" | "FEN");
+            if !reserved {
+                out += &tag_line(name, value);
+            }
+        }
+        out.push('\n');
+
+        // movetext
+        let mut tokens = Vec::with_capacity(self.moves.len() * 2 + 1);
+        let mut pos = self.start.clone();
+        for (i, &mv) in self.moves.iter().enumerate() {
+            if pos.side_to_move() == Color::White {
+                tokens.push(format!("{}.", pos.full_move()));
+            } else if i == 0 {
+                tokens.push(format!("{}...", pos.full_move()));
+            }
+            tokens.push(pos.move_to_san(mv));
+            if let Some(Some(eval)) = self.evals.get(i) {
+                tokens.push(format!("{{{:+.2}/{}}}", eval.score as f32 / 100.0, eval.depth));
+            }
+            play(&mut pos, mv);
+        }
+        tokens.push(result_str(self.result).to_string());
+
+        let mut line_len = 0;
+        for token in tokens {
+            if line_len > 0 && line_len + 1 + token.len() > LINE_WIDTH {
+                out.push('\n');
+                line_len = 0;
+            } else if line_len > 0 {
+                out.push(' ');
+                line_len += 1;
+            }
+            line_len += token.len();
+            out += &token;
+        }
+        out += "\n";
+        out
+    }
+}
+
+pub(crate) fn play(pos: &mut Position, mv: Move) {
+    if pos.undo_stack.is_near_full() {
+        pos.undo_stack.make_space();
+    }
+    pos.do_move(mv);
+}
+
+fn tag_line(name: &str, value: &str) -> String {
+    let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("[{name} \"{value}\"]\n")
+}
+
+fn result_str(result: Status) -> &'static str {
+    match result {
+        Status::Checkmate(Color::White) => "1-0",
+        Status::Checkmate(Color::Black) => "0-1",
+        Status::Draw                    => "1/2-1/2",
+        Status::Ongoing                 => "*",
+    }
+}
+
+fn result_from_str(token: &str) -> Option<Status> {
+    match token {
+        "1-0"     => Some(Status::Checkmate(Color::White)),
+        "0-1"     => Some(Status::Checkmate(Color::Black)),
+        "1/2-1/2" => Some(Status::Draw),
+        "*"       => Some(Status::Ongoing),
+        _ => None,
+    }
+}
+
+
+/// Why a PGN file could not be read. `game` counts from 1.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PgnError {
+    Fen    { game: usize, error: FenError },
+    Move   { game: usize, ply: usize, error: MoveParseError },
+    Syntax { game: usize, line: usize, message: &'static str },
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnError::Fen { game, error }         => write!(f, "game {game}: bad FEN tag: {error}"),
+            PgnError::Move { game, ply, error }   => write!(f, "game {game}, ply {ply}: {error}"),
+            PgnError::Syntax { game, line, message } => write!(f, "game {game}, line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+
+/// Lexical pieces of a PGN file.
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Tag(&'a str, String),
+    Comment(&'a str),
+    Open,
+    Close,
+    Word(&'a str),  // move number, SAN, NAG or result
+}
+
+/// Parses every game in `text`. Comments other than `{eval/depth}` ones, NAGs and
+/// (recursive) variations are skipped; only the main line is kept.
+pub fn parse_pgn(text: &str) -> Result<Vec<Game>, PgnError> {
+    let mut games = Vec::new();
+    let mut current: Option<(Game, Position)> = None;
+    let mut in_movetext = false;
+    let mut variation_depth = 0usize;
+
+    for (line_no, token) in tokenize(text)? {
+        let game_no = games.len() + 1;
+        let syntax = |message| PgnError::Syntax { game: game_no, line: line_no, message };
+        let mut finished = false;
+
+        // a tag after movetext without a result token starts the next game
+        if matches!(token, Token::Tag(..)) && in_movetext {
+            let (game, _) = current.take().expect("movetext belongs to a game");
+            games.push(game);
+            in_movetext = false;
+        }
+        let (game, pos) = current.get_or_insert_with(|| {
+            let start = Position::load_position_from_fen(START_FEN);
+            (Game::new(start.clone()), start)
+        });
+
+        match token {
+            Token::Tag(name, value) => {
+                if name == "FEN" {
+                    let start = Position::from_fen(&value)
+                        .map_err(|error| PgnError::Fen { game: game_no, error })?;
+                    game.start = start.clone();
+                    *pos = start;
+                }
+                game.set_tag(name, &value);
+            }
+            Token::Open => {
+                variation_depth += 1;
+                in_movetext = true;
+            }
+            Token::Close => {
+                variation_depth = variation_depth.checked_sub(1).ok_or_else(|| syntax("unmatched ')'"))?;
+            }
+            _ if variation_depth > 0 => {}
+            Token::Comment(comment) => {
+                if let (Some(eval), Some(slot)) = (parse_eval(comment), game.evals.last_mut()) {
+                    *slot = Some(eval);
+                }
+            }
+            Token::Word(word) => {
+                in_movetext = true;
+                if let Some(result) = result_from_str(word) {
+                    game.result = result;
+                    finished = true;
+                } else if word.starts_with('$') || word.bytes().all(|b| b.is_ascii_digit()) {
+                    // NAG or bare move number
+                } else {
+                    let mv = pos.parse_san(word).map_err(|error| PgnError::Move {
+                        game: game_no,
+                        ply: game.moves.len() + 1,
+                        error,
+                    })?;
+                    play(pos, mv);
+                    game.push(mv, None);
+                }
+            }
+        }
+
+        if finished {
+            let (game, _) = current.take().expect("result belongs to a game");
+            games.push(game);
+            in_movetext = false;
+        }
+    }
+
+    if variation_depth > 0 {
+        return Err(PgnError::Syntax { game: games.len() + 1, line: text.lines().count(), message: "unclosed '('" });
+    }
+    if let Some((game, _)) = current
+        && (in_movetext || !game.tags.is_empty())
+    {
+        games.push(game);
+    }
+    Ok(games)
+}
+
+/// Reads an engine comment such as `+0.35/12` or `-1.20/8 0.5s`.
+fn parse_eval(comment: &str) -> Option<MoveEval> {
+    let (score, depth) = comment.split_whitespace().next()?.split_once('/')?;
+    let score: f32 = score.parse().ok()?;
+    Some(MoveEval { score: (score * 100.0).round() as i16, depth: depth.parse().ok()? })
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token<'_>)>, PgnError> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut game = 1;  // only for error messages
+    let mut rest = text;
+
+    while let Some(ch) = rest.chars().next() {
+        match ch {
+            '\n' => {
+                line += 1;
+                rest = &rest[1..];
+            }
+            c if c.is_whitespace() => rest = &rest[c.len_utf8()..],
+            // `;` comments run to the end of the line, a `%` in the first column escapes the whole line
+            ';' => rest = rest.find('\n').map_or("", |i| &rest[i..]),
+            '%' if text[..text.len() - rest.len()].ends_with('\n') || rest.len() == text.len() => {
+                rest = rest.find('\n').map_or("", |i| &rest[i..]);
+            }
+            '{' => {
+                let end = rest.find('}').ok_or(PgnError::Syntax { game, line, message: "unterminated comment" })?;
+                tokens.push((line, Token::Comment(rest[1..end].trim())));
+                line += rest[..end].matches('\n').count();
+                rest = &rest[end + 1..];
+            }
+            '[' => {
+                let (tag, len) = read_tag(rest).ok_or(PgnError::Syntax { game, line, message: "malformed tag" })?;
+                tokens.push((line, tag));
+                rest = &rest[len..];
+            }
+            '(' => {
+                tokens.push((line, Token::Open));
+                rest = &rest[1..];
+            }
+            ')' => {
+                tokens.push((line, Token::Close));
+                rest = &rest[1..];
+            }
+            _ => {
+                let end = rest.find(|c: char| c.is_whitespace() || "{}[]();".contains(c)).unwrap_or(rest.len());
+                let word = &rest[..end];
+                rest = &rest[end..];
+
+                // "12.e4" and "12...e5" carry the move on the number
+                let word = match word.find('.') {
+                    Some(_) if word.starts_with(|c: char| c.is_ascii_digit()) => word.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.'),
+                    _ => word,
+                };
+                if result_from_str(word).is_some() {
+                    game += 1;
+                }
+                if !word.is_empty() {
+                    tokens.push((line, Token::Word(word)));
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Reads `[Name "value"]` from the start of `text`; returns the tag and its length in bytes.
+fn read_tag(text: &str) -> Option<(Token<'_>, usize)> {
+    let inner = text[1..].trim_start();
+    let name_len = inner.find(|c: char| !c.is_ascii_alphanumeric() && c != '_')?;
+    let name = &inner[..name_len];
+    let after_name = inner[name_len..].trim_start().strip_prefix('"')?;
+
+    let mut value = String::new();
+    let mut chars = after_name.char_indices();
+    let close_quote = loop {
+        match chars.next()? {
+            (_, '\\') => value.push(chars.next()?.1),
+            (i, '"') => break i,
+            (_, c) => value.push(c),
+        }
+    };
+    let after_value = &after_name[close_quote + 1..];
+    let tail = after_value.trim_start().strip_prefix(']')?;
+    Some((Token::Tag(name, value), text.len() - tail.len()))
+}
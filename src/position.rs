@@ -1,6 +1,7 @@
 #![cfg_attr(not(debug_assertions), allow(unused_unsafe))]
 
 use std::str::SplitWhitespace;
+use std::sync::Arc;
 use crate::attacks::movegen::{all_moves};
 use crate::attacks::sliding::{diagonal_attacks, orthogonal_attacks};
 use crate::bitboards::{FILE_A, FILE_B, FILE_C, FILE_D, FILE_E, FILE_F, FILE_G, FILE_H};
@@ -10,7 +11,11 @@ use crate::color::Color::{Black, White};
 use crate::tables::{zobrist, KING_MOVES, KNIGHT_MOVES, PAWN_ATTACKS, RAYS};
 use crate::mov::{en_passant_capture_pawn, flag, index_to_algebraic, is_flag_capture_promo, is_flag_quiet_promo, new_en_passant_square, Move};
 use crate::direction::Dir;
-use crate::eval::{build_eval, mirror, EvalCache, EG_VALUE, MG_VALUE, PHASE_INC, PST_EG, PST_MG};
+use crate::fen::{self, FenError};
+use crate::engines::nnue::{Accumulator, Network};
+use crate::engines::score::mate_in;
+use crate::eval::{build_eval, mirror, EvalCache, EG_PARAMS, EG_VALUE, MG_VALUE, PHASE_INC, PST_EG, PST_MG, SIMPLIFY_INDEX};
+use crate::eval_params::EVAL_PARAMS;
 use crate::piece::{is_empty, is_slider_val, piece_to_val, to_color, to_piece, to_str, ColoredPiece, Piece, EMPTY_PIECE, PIECE_SCORES, SEE_SCORES};
 use crate::position::Status::{Checkmate, Draw, Ongoing};
 pub(crate) use crate::state_info::StateInfo;
@@ -37,12 +42,15 @@ pub struct Position {
     occupancy: [u64; 2],
     pub(crate) undo_stack: UndoStack,
     zobrist: u64,
+    pawn_key: u64,  // zobrist of the pawns alone, for the pawn hash
     eval: EvalCache,
+    nnue: Option<Box<Accumulator>>,  // only while a network is attached
     turn: Color,
     castling_rights: CastlingRights,
     state_info: StateInfo,
     en_passant: u8,
     half_move: u8,
+    full_move: u16,
 }
 impl Default for Position {
     fn default() -> Self {
@@ -55,19 +63,35 @@ impl Default for Position {
             occupancy: [0u64; 2],
             undo_stack: UndoStack::new(),
             zobrist: 0,
+            pawn_key: 0,
             eval: EvalCache::default(),
+            nnue: None,
             turn: Default::default(),
             castling_rights: Default::default(),
             state_info: Default::default(),
             en_passant: 0,
             half_move: 0,
+            full_move: 1,
         }
     }
 }
 
 impl Position {
     pub fn start() -> Position {
-        Self::load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 0")
+        Self::load_position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
+    }
+
+    /// Parses and validates a FEN string. Unlike `load_position_from_fen`, malformed or
+    /// illegal input is reported as a `FenError` instead of panicking.
+    pub fn from_fen(fen: &str) -> Result<Position, FenError> {
+        fen::validate(fen)?;
+        let position = Self::load_position_from_fen(fen);
+
+        let them = !position.turn;
+        if position.square_under_attack(position.king_square(them), position.turn) {
+            return Err(FenError::OpponentInCheck { offset: fen.len() - fen.trim_start().len() });
+        }
+        Ok(position)
     }
 
     pub fn load_position_from_fen(fen: &str) -> Position {
@@ -97,11 +121,17 @@ impl Position {
         };
 
 
+        // past 100 the clock only has to say "drawn", so larger ones saturate
         let half_move_str: Option<&str> = iter.next();
         position.half_move = half_move_str
-            .map(|s| s.parse::<u8>().expect("Invalid half move count"))
+            .map(|s| s.parse::<u16>().expect("Invalid half move count").min(u8::MAX as u16) as u8)
             .unwrap_or(0);
-        position.half_move = 0;
+
+        // some tools write 0 for the first move
+        let full_move_str: Option<&str> = iter.next();
+        position.full_move = full_move_str
+            .map(|s| s.parse::<u16>().expect("Invalid full move number").max(1))
+            .unwrap_or(1);
 
         if position.turn == Color::Black {
             position.zobrist ^= zobrist::TURN_IS_BLACK;
@@ -188,6 +218,10 @@ impl Position {
         // half move
         s += " ";
         s += &*self.half_move.to_string();
+
+        // full move
+        s += " ";
+        s += &*self.full_move.to_string();
         s
     }
 
@@ -257,6 +291,7 @@ impl Position {
             en_passant:  self.en_passant,
             half_move:   self.half_move,
             zobrist:     self.zobrist,
+            pawn_key:    self.pawn_key,
             delta_raw_piece_diff: 0,
             delta_mg:    0,
             delta_eg:    0,
@@ -275,6 +310,18 @@ impl Position {
         self.zobrist ^= zobrist::PIECE_SQUARES[from][p_idx][color as usize];
         self.zobrist ^= zobrist::PIECE_SQUARES[to  ][p_idx][color as usize];
 
+        /* ---- pawn key: pawns that moved, were captured or promoted ---- */
+        if piece == Piece::Pawn {
+            self.pawn_key ^= zobrist::PIECE_SQUARES[from][p_idx][color as usize];
+            if !is_flag_quiet_promo(flag) && !is_flag_capture_promo(flag) {
+                self.pawn_key ^= zobrist::PIECE_SQUARES[to][p_idx][color as usize];
+            }
+        }
+        if captured_piece != EMPTY_PIECE && to_piece(captured_piece) == Piece::Pawn {
+            let cap_c = to_color(captured_piece) as usize;
+            self.pawn_key ^= zobrist::PIECE_SQUARES[captured_square as usize][Piece::Pawn as usize][cap_c];
+        }
+
         /* 5. main move ladder (unchanged logic) --------------------- */
         if flag == flag::QUIET {
             self.move_piece(piece, color, from, to);
@@ -362,9 +409,13 @@ impl Position {
         self.half_move = if piece == Piece::Pawn || captured_piece != EMPTY_PIECE {
             0
         } else {
-            self.half_move + 1
+            self.half_move.saturating_add(1)
         };
 
+        if color == Black {
+            self.full_move += 1;
+        }
+
         /* 11. flip turn & hash ---------------------------------------- */
         self.zobrist ^= zobrist::TURN_IS_BLACK;
         self.turn = !self.turn;
@@ -414,6 +465,7 @@ impl Position {
 
         /* 3. restore hash, rights, counters --------------------------- */
         self.zobrist         = undo.zobrist;
+        self.pawn_key        = undo.pawn_key;
         self.castling_rights = undo.castling;
         self.en_passant      = undo.en_passant;
         self.half_move       = undo.half_move;
@@ -429,6 +481,9 @@ impl Position {
 
         /* 5. flip side-to-move ---------------------------------------- */
         self.turn = !self.turn;
+        if self.turn == Black {
+            self.full_move -= 1;
+        }
 
     }
 
@@ -444,6 +499,10 @@ impl Position {
         let cap_c = to_color(self.board[to]) as usize;
 
         self.zobrist ^= zobrist::PIECE_SQUARES[to][cap_p][cap_c];
+        if let Some(acc) = &mut self.nnue {
+            acc.remove(Piece::from(cap_p), to_color(self.board[to]), to);
+            acc.move_piece(piece, color, from, to);
+        }
 
 
         let idx        = self.rev_idx(cap_p, cap_c, to) as usize;
@@ -530,6 +589,27 @@ impl Position {
         self.bitboards[color as usize][piece as usize] ^= delta;
         self.occupancy[color as usize]                 ^= delta;
 
+        if let Some(acc) = &mut self.nnue {
+            acc.move_piece(piece, color, from, to);
+        }
+
+    }
+
+    /// Moves the piece on `from` to the empty square `to` outside of play: nothing is pushed
+    /// on the undo stack and the side to move stays. Lets generators walk one set-up through
+    /// many placements without parsing a FEN for each.
+    pub(crate) fn relocate_piece(&mut self, from: u8, to: u8) {
+        let piece = self.piece_at_sq(from);
+        let color = to_color(self.board[from as usize]);
+        self.move_piece(piece, color, from as usize, to as usize);
+        self.zobrist ^= zobrist::PIECE_SQUARES[from as usize][piece as usize][color as usize];
+        self.zobrist ^= zobrist::PIECE_SQUARES[to as usize][piece as usize][color as usize];
+        if piece == Piece::Pawn {
+            self.pawn_key ^= zobrist::PIECE_SQUARES[from as usize][piece as usize][color as usize];
+            self.pawn_key ^= zobrist::PIECE_SQUARES[to as usize][piece as usize][color as usize];
+        }
+        self.eval = build_eval(self);
+        self.state_info = self.compute_pins_checks(self.turn);
     }
 
     #[inline(always)]
@@ -539,6 +619,10 @@ impl Position {
         let old_c = to_color(self.board[sq])  as usize;
 
         self.zobrist ^= zobrist::PIECE_SQUARES[sq][old_p][old_c];
+        if let Some(acc) = &mut self.nnue {
+            acc.remove(Piece::from(old_p), to_color(self.board[sq]), sq);
+            acc.add(new_piece, new_color, sq);
+        }
 
 
         let idx       = self.rev_idx(old_p, old_c, sq) as usize;
@@ -588,6 +672,10 @@ impl Position {
         let bb = 1u64 << sq;
         self.bitboards[c][p] |= bb;
         self.occupancy[c] |= bb;
+
+        if let Some(acc) = &mut self.nnue {
+            acc.add(to_piece(new_piece), to_color(new_piece), sq as usize);
+        }
     }
 
     #[inline(always)]
@@ -596,6 +684,9 @@ impl Position {
         let c = to_color(self.board[sq]) as usize;
 
         self.zobrist ^= zobrist::PIECE_SQUARES[sq][p][c];
+        if let Some(acc) = &mut self.nnue {
+            acc.remove(Piece::from(p), to_color(self.board[sq]), sq);
+        }
 
 
         // index of the captured piece in the list
@@ -652,10 +743,10 @@ impl Position {
         // 3) How simplified are we? (0 at start, grows as pieces come off)
         let simplified: i32 = (MAX_NONPAWN_START - nonpawn_count).max(0);
 
-        // 4) Tunable coefficients (in CP per piece of simplification).
+        // 4) Tuned coefficients (in CP per piece of simplification).
         //    Stronger in endgame, milder in middlegame.
-        const SIMPLIFY_MG: i32 = 2;  // cp per piece of simplification when ahead
-        const SIMPLIFY_EG: i32 = 6;  // cp per piece of simplification when ahead
+        const SIMPLIFY_MG: i32 = EVAL_PARAMS[SIMPLIFY_INDEX] as i32;
+        const SIMPLIFY_EG: i32 = EVAL_PARAMS[EG_PARAMS + SIMPLIFY_INDEX] as i32;
 
         // Bias sign follows the material lead:
         //  - If material_lead_cp > 0 => bonus for simplification (trading).
@@ -694,10 +785,10 @@ impl Position {
         // 3) How simplified are we? (0 at start, grows as pieces come off)
         let simplified: i32 = (MAX_NONPAWN_START - nonpawn_count).max(0);
 
-        // 4) Tunable coefficients (in CP per piece of simplification).
+        // 4) Tuned coefficients (in CP per piece of simplification).
         //    Stronger in endgame, milder in middlegame.
-        const SIMPLIFY_MG: i32 = 2;  // cp per piece of simplification when ahead
-        const SIMPLIFY_EG: i32 = 6;  // cp per piece of simplification when ahead
+        const SIMPLIFY_MG: i32 = EVAL_PARAMS[SIMPLIFY_INDEX] as i32;
+        const SIMPLIFY_EG: i32 = EVAL_PARAMS[EG_PARAMS + SIMPLIFY_INDEX] as i32;
 
         // Bias sign follows the material lead:
         //  - If material_lead_cp > 0 => bonus for simplification (trading).
@@ -792,13 +883,14 @@ impl Position {
 
 
     #[inline(always)]
-    pub fn game_result_eval(&self, depth: u8) -> i16 {
-        // Unchecked function to evaluate a finished chess game. Cannot be called on ongoing games.
+    pub fn game_result_eval(&self, ply: u16) -> i16 {
+        // Unchecked function to evaluate a finished chess game, `ply` half-moves from the root.
+        // Cannot be called on ongoing games.
         let result = self.get_game_result();
 
         match result {
-            Checkmate(White) =>  10000 + (depth as i16),
-            Checkmate(Black) => -10000 - (depth as i16),
+            Checkmate(White) =>  mate_in(ply),
+            Checkmate(Black) => -mate_in(ply),
             Draw             =>  0,
             Ongoing          =>  !unreachable!(),
         }
@@ -937,7 +1029,7 @@ impl Position {
     }
 
 
-    fn raw_material_diff(&self) -> i32 {
+    pub(crate) fn raw_material_diff(&self) -> i32 {
         self.eval.raw_mat_diff
         // const VALUES: [i32; 6] = [  100, 320, 330, 500, 900,   0];
         //
@@ -1228,11 +1320,43 @@ impl Position {
         self.zobrist
     }
 
+    #[inline(always)]
+    pub fn pawn_key(&self) -> u64 {
+        self.pawn_key
+    }
+
+    /// Middlegame weight of the tapered eval: 24 with all pieces on, 0 in pawn endings.
+    #[inline(always)]
+    pub(crate) fn mg_phase(&self) -> i32 {
+        self.eval.phase.clamp(0, 24)
+    }
+
+    /// Attaches an NNUE network (or detaches it with `None`). While attached, its
+    /// accumulators are updated with every piece that is added, removed or moved.
+    pub fn set_network(&mut self, network: Option<Arc<Network>>) {
+        self.nnue = network.map(|network| Box::new(Accumulator::new(network, self)));
+    }
+
+    pub fn network(&self) -> Option<&Arc<Network>> {
+        self.nnue.as_ref().map(|acc| acc.network())
+    }
+
+    /// The attached network's evaluation from White's point of view.
+    #[inline(always)]
+    pub fn evaluate_nnue(&self) -> Option<i16> {
+        self.nnue.as_ref().map(|acc| acc.evaluate(self.turn))
+    }
+
     #[inline(always)]
     pub fn half_move(&self) -> u8 {
         self.half_move
     }
 
+    #[inline(always)]
+    pub fn full_move(&self) -> u16 {
+        self.full_move
+    }
+
     #[inline(always)]
     pub fn state_info(&self) -> StateInfo {
         self.state_info
@@ -1391,6 +1515,9 @@ fn update_bitboards_pieces(position: &mut Position, ch: char, square: u8) {
     let c = to_color(colored_piece) as usize;
 
     position.zobrist ^= zobrist::PIECE_SQUARES[square as usize][p][c];
+    if p == Piece::Pawn as usize {
+        position.pawn_key ^= zobrist::PIECE_SQUARES[square as usize][p][c];
+    }
 
     let count = position.piece_count[p][c];
     position.piece_list[p][c][count] = square;
@@ -0,0 +1,366 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Instant;
+use indicatif::{ProgressBar, ProgressStyle};
+use rand::{rng, Rng};
+use crate::attacks::movegen::all_moves;
+use crate::color::Color;
+use crate::engines::engine_manager::{Engine, SearchLimits, NUMBER_OF_EVAL_ALGORITHMS};
+use crate::engines::nnue::Network;
+use crate::engines::score::Score;
+use crate::piece::Piece;
+use crate::position::{Position, Status};
+use crate::simulator::even_fens::EVEN_FENS;
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Bytes per position in the binary format; see [`pack`].
+pub const RECORD_SIZE: usize = 32;
+
+// like the simulator: every thread has its own engine, so keep the tables small
+const DATAGEN_HASH_MB: usize = 16;
+/// Games longer than this many plies are called drawn.
+const MAX_GAME_PLIES: usize = 400;
+
+
+/// Packs a position with its search score (White's point of view, centipawns) and the game
+/// result (1.0 White won, 0.5 draw, 0.0 Black won) into [`RECORD_SIZE`] bytes:
+///
+/// - occupancy, u64
+/// - one nibble per occupied square, lowest square first and low nibble first:
+///   piece (0 pawn .. 5 king), plus 8 for Black
+/// - score, i16
+/// - result, u8: 2 White won, 1 draw, 0 Black won
+/// - side to move, u8: 0 White, 1 Black
+/// - half-move clock, u8, and full-move number, u16
+/// - one reserved zero byte
+///
+/// All little-endian. Castling rights and the en passant square are not kept: the
+/// positions are quiet, and networks do not look at either.
+pub fn pack(pos: &Position, score: i16, result: f32) -> [u8; RECORD_SIZE] {
+    let mut bytes = [0u8; RECORD_SIZE];
+    let occupied = pos.occupied();
+    bytes[0..8].copy_from_slice(&occupied.to_le_bytes());
+
+    let mut squares = occupied;
+    let mut i = 0;
+    while squares != 0 {
+        let sq = squares.trailing_zeros() as u8;
+        squares &= squares - 1;
+        let black = pos.occupancy(Color::Black) & (1 << sq) != 0;
+        let nibble = pos.piece_at_sq(sq) as u8 | (black as u8) << 3;
+        bytes[8 + i / 2] |= nibble << (4 * (i % 2));
+        i += 1;
+    }
+
+    bytes[24..26].copy_from_slice(&score.to_le_bytes());
+    set_result(&mut bytes, result);
+    bytes[27] = !pos.side_to_move().is_white() as u8;
+    bytes[28] = pos.half_move();
+    bytes[29..31].copy_from_slice(&pos.full_move().to_le_bytes());
+    bytes
+}
+
+/// Overwrites the game result of a packed position.
+fn set_result(bytes: &mut [u8; RECORD_SIZE], result: f32) {
+    bytes[26] = (result * 2.0).round() as u8;
+}
+
+
+/// One training position as read back from either format.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub fen:    String,
+    pub score:  i16,   // White's point of view
+    pub result: f32,   // 1.0 White won, 0.5 draw, 0.0 Black won
+}
+
+impl Record {
+    pub fn from_bytes(bytes: &[u8; RECORD_SIZE]) -> Record {
+        let mut occupied = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let mut board = ['.'; 64];
+        let mut i = 0;
+        while occupied != 0 {
+            let sq = occupied.trailing_zeros() as usize;
+            occupied &= occupied - 1;
+            let nibble = (bytes[8 + i / 2] >> (4 * (i % 2))) & 0xF;
+            let initial = Piece::from((nibble & 7) as usize).piece_initial();
+            board[sq] = if nibble & 8 == 0 { initial.to_ascii_uppercase() } else { initial };
+            i += 1;
+        }
+
+        let mut placement = String::new();
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+            for file in 0..8 {
+                match board[rank * 8 + file] {
+                    '.' => empty += 1,
+                    c => {
+                        if empty > 0 {
+                            placement.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        placement.push(c);
+                    }
+                }
+            }
+            if empty > 0 {
+                placement.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                placement.push('/');
+            }
+        }
+
+        let side = if bytes[27] == 0 { 'w' } else { 'b' };
+        let full_move = u16::from_le_bytes([bytes[29], bytes[30]]);
+        Record {
+            fen:    format!("{placement} {side} - - {} {full_move}", bytes[28]),
+            score:  i16::from_le_bytes([bytes[24], bytes[25]]),
+            result: bytes[26] as f32 / 2.0,
+        }
+    }
+
+    /// The text format: `<fen> | <score> | <result>`, one position per line.
+    pub fn to_text(&self) -> String {
+        format!("{} | {} | {:.1}", self.fen, self.score, self.result)
+    }
+
+    pub fn from_text(line: &str) -> Option<Record> {
+        let mut fields = line.split('|').map(str::trim);
+        let fen    = fields.next()?.to_string();
+        let score  = fields.next()?.parse().ok()?;
+        let result = fields.next()?.parse().ok()?;
+        Some(Record { fen, score, result })
+    }
+}
+
+
+/// Self-play data generation: plays engine-vs-engine games from random openings and keeps
+/// the quiet positions (side to move not in check, best move not a capture, no mate score)
+/// together with the search score and the final result of the game.
+pub struct Datagen {
+    pub positions:    u64,   // stop once the output holds this many
+    pub threads:      usize,
+    pub nodes:        u64,   // per move
+    pub search_algo:  u8,
+    pub eval_algo:    u8,
+    pub network:      Option<Arc<Network>>,
+    pub startpos:     bool,  // open from the start position instead of an EVEN_FENS entry
+    pub random_plies: u8,    // random moves played before the engines take over
+}
+
+impl Default for Datagen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Datagen {
+    pub fn new() -> Datagen {
+        Datagen {
+            positions:    1_000_000,
+            threads:      thread::available_parallelism().map_or(1, |n| n.get()),
+            nodes:        5_000,
+            search_algo:  29,
+            eval_algo:    NUMBER_OF_EVAL_ALGORITHMS,
+            network:      None,
+            startpos:     false,
+            random_plies: 4,
+        }
+    }
+
+    /// Generates into `binary` (and `text`, if given) until they hold `positions` positions.
+    /// Existing files are resumed: whatever they both already hold counts, and positions
+    /// only one of them got before an interrupted run stopped are dropped. Returns the
+    /// number of positions added.
+    pub fn run(&self, binary: &Path, text: Option<&Path>) -> io::Result<u64> {
+        let existing = resume(binary, text)?;
+        if existing >= self.positions {
+            return Ok(0);
+        }
+
+        let output = Mutex::new(Output {
+            binary:  BufWriter::new(OpenOptions::new().append(true).open(binary)?),
+            text:    text.map(|path| OpenOptions::new().append(true).create(true).open(path).map(BufWriter::new)).transpose()?,
+            written: existing,
+            target:  self.positions,
+            error:   None,
+        });
+        let done = AtomicBool::new(false);
+
+        let start = Instant::now();
+        let pb = ProgressBar::new(self.positions).with_style(
+            ProgressStyle::with_template(
+                "{spinner:.green} {elapsed_precise} [{wide_bar:.cyan/blue}] \
+                 {pos}/{len} • ETA {eta_precise}",
+            )
+                .unwrap()
+                .progress_chars("=>-"),
+        );
+        pb.set_position(existing);
+
+        let mut engine = Engine::new(self.search_algo, self.eval_algo, 0);
+        engine.set_hash_mb(DATAGEN_HASH_MB);
+        engine.set_network(self.network.clone());
+        engine.set_limits(SearchLimits { nodes: Some(self.nodes), infinite: true, ..SearchLimits::default() });
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads.max(1) {
+                let mut engine = engine.clone();
+                let (output, done, pb) = (&output, &done, pb.clone());
+                scope.spawn(move || {
+                    while !done.load(Ordering::Relaxed) {
+                        let game = self.play_game(&mut engine);
+                        let mut output = output.lock().unwrap();
+                        if output.write(&game) {
+                            done.store(true, Ordering::Relaxed);
+                        }
+                        pb.set_position(output.written);
+                    }
+                });
+            }
+        });
+
+        let mut output = output.into_inner().unwrap();
+        if let Some(e) = output.error.take() {
+            return Err(e);
+        }
+        output.binary.flush()?;
+        if let Some(text) = output.text.as_mut() {
+            text.flush()?;
+        }
+        pb.finish_with_message(format!("Done in {:?}", start.elapsed()));
+        Ok(output.written - existing)
+    }
+
+    /// One game; returns its quiet positions, packed as soon as they are played so that a
+    /// game does not keep a copy of the whole move history for each of them.
+    fn play_game(&self, engine: &mut Engine) -> Vec<[u8; RECORD_SIZE]> {
+        let mut rng = rng();
+        let mut position = self.opening(&mut rng);
+        engine.clear_hash();
+
+        let mut quiet = Vec::new();
+        let mut plies = 0;
+        while !all_moves(&position).is_empty() {
+            if plies >= MAX_GAME_PLIES || position.is_three_fold_repetition() || position.half_move_over_ninety_nine() {
+                return quiet;
+            }
+            plies += 1;
+
+            let (mv, _, score) = engine.pick_and_stats(&mut position);
+            if let Score::Cp(cp) = score && !position.in_check() && !mv.is_capture() {
+                let cp = if position.side_to_move().is_white() { cp } else { -cp };
+                // the result is only known at the end; a draw until then
+                quiet.push(pack(&position, cp, 0.5));
+            }
+            position.do_move(mv);
+        }
+        let result = match position.get_game_result() {
+            Status::Checkmate(Color::White) => 1.0,
+            Status::Checkmate(Color::Black) => 0.0,
+            _                               => 0.5,
+        };
+        quiet.iter_mut().for_each(|bytes| set_result(bytes, result));
+        quiet
+    }
+
+    /// A balanced position followed by `random_plies` random moves; tried again whenever
+    /// the random moves end the game.
+    fn opening(&self, rng: &mut impl Rng) -> Position {
+        'retry: loop {
+            let fen = if self.startpos {
+                START_FEN
+            } else {
+                EVEN_FENS[rng.random_range(0..EVEN_FENS.len())]
+            };
+            let mut position = Position::load_position_from_fen(fen);
+            for _ in 0..self.random_plies {
+                let moves = all_moves(&position);
+                if moves.is_empty() {
+                    continue 'retry;
+                }
+                position.do_move(moves.random());
+            }
+            if !all_moves(&position).is_empty() {
+                return position;
+            }
+        }
+    }
+}
+
+
+/// The output files, shared by the worker threads; whole games are written at once.
+struct Output {
+    binary:  BufWriter<File>,
+    text:    Option<BufWriter<File>>,
+    written: u64,
+    target:  u64,
+    error:   Option<io::Error>,
+}
+
+impl Output {
+    /// Writes a game's positions, up to the target. Returns `true` once generation is over.
+    fn write(&mut self, positions: &[[u8; RECORD_SIZE]]) -> bool {
+        for bytes in positions {
+            if self.written >= self.target {
+                break;
+            }
+            let written = self.binary.write_all(bytes).and_then(|_| match self.text.as_mut() {
+                Some(text) => writeln!(text, "{}", Record::from_bytes(bytes).to_text()),
+                None       => Ok(()),
+            });
+            if let Err(e) = written {
+                self.error = Some(e);
+                return true;
+            }
+            self.written += 1;
+        }
+        // a game at a time, so that an interrupted run loses at most the one being written
+        let flushed = self.binary.flush().and_then(|_| self.text.as_mut().map_or(Ok(()), |text| text.flush()));
+        if let Err(e) = flushed {
+            self.error = Some(e);
+            return true;
+        }
+        self.written >= self.target
+    }
+}
+
+/// Creates the output files or cuts them back to the positions both hold in full; returns
+/// how many that is.
+fn resume(binary: &Path, text: Option<&Path>) -> io::Result<u64> {
+    let file = OpenOptions::new().write(true).create(true).truncate(false).open(binary)?;
+    let mut records = file.metadata()?.len() / RECORD_SIZE as u64;
+
+    if let Some(text) = text {
+        if !text.exists() && records > 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} has positions but there is no {} to go with them", binary.display(), text.display()),
+            ));
+        }
+        let mut lines = Vec::new();  // byte length of each complete line
+        if let Ok(file) = File::open(text) {
+            let mut reader = BufReader::new(file);
+            let mut line = Vec::new();
+            while (lines.len() as u64) < records && reader.read_until(b'\n', &mut line)? > 0 {
+                if line.last() != Some(&b'\n') {
+                    break;
+                }
+                lines.push(line.len() as u64);
+                line.clear();
+            }
+        }
+        records = records.min(lines.len() as u64);
+        OpenOptions::new().write(true).create(true).truncate(false).open(text)?
+            .set_len(lines[..records as usize].iter().sum())?;
+    }
+
+    file.set_len(records * RECORD_SIZE as u64)?;
+    Ok(records)
+}
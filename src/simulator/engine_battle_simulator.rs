@@ -1,19 +1,26 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::thread;
 use std::time::Instant;
 use indicatif::{ProgressBar, ProgressStyle};
 use rand::{rng, Rng};
-use rand::prelude::ThreadRng;
 use crate::attacks::movegen::all_moves;
 use crate::color::Color;
 use crate::engines::engine_manager::{Engine, NUMBER_OF_EVAL_ALGORITHMS, NUMBER_OF_SEARCH_ALGORITHMS};
 use crate::simulator::even_fens::EVEN_FENS;
+use crate::simulator::stats::{MatchStats, Sprt, SprtDecision};
 use crate::position::{Status, Position};
 use crate::position::Status::{Checkmate, Draw};
 
+// every worker thread clones both engines, so keep their tables small
+const SIMULATOR_HASH_MB: usize = 16;
+const NUM_THREADS: u16 = 15;
 
 
-pub  fn battle_against_other_eval_algos(search_algo: u8, eval_algo: u8, time_per_move: u64, num_battles: u16) {
+
+pub  fn battle_against_other_eval_algos(search_algo: u8, eval_algo: u8, time_per_move: u64, num_pairs: u16, openings: Openings) {
     let mut champion = Engine::new(search_algo, eval_algo, time_per_move);
+    champion.set_hash_mb(SIMULATOR_HASH_MB);
     println!("\
     \n\n\n\n                      SIMULATING ENGINE\
     \n                     [search: {search_algo}, eval: {eval_algo}]
@@ -21,15 +28,18 @@ pub  fn battle_against_other_eval_algos(search_algo: u8, eval_algo: u8, time_per
     for i in NUMBER_OF_EVAL_ALGORITHMS-1..NUMBER_OF_EVAL_ALGORITHMS+1 {
         if i != eval_algo {
             let mut challenger = Engine::new(search_algo, i, time_per_move);
-            let (wins, losses, draws) = simulate_many_battles(&mut challenger, &mut champion, num_battles);
-            print_bar_graph(wins, losses, draws, search_algo, i);
+            challenger.set_hash_mb(SIMULATOR_HASH_MB);
+            let (stats, pairs) = simulate_many_battles(&mut challenger, &mut champion, num_pairs, openings);
+            print_pairs(&pairs);
+            print_bar_graph(&stats, search_algo, i);
         }
     }
 }
 
 
-pub fn battle_against_other_search_algos(search_algo: u8, eval_algo: u8, time_per_move: u64, num_battles: u16) {
+pub fn battle_against_other_search_algos(search_algo: u8, eval_algo: u8, time_per_move: u64, num_pairs: u16, openings: Openings) {
     let mut champion = Engine::new(search_algo, eval_algo, time_per_move);
+    champion.set_hash_mb(SIMULATOR_HASH_MB);
     println!("\
     \n\n\n\n                      SIMULATING ENGINE\
     \n                     [search: {search_algo}, eval: {eval_algo}]
@@ -37,14 +47,17 @@ pub fn battle_against_other_search_algos(search_algo: u8, eval_algo: u8, time_pe
     for i in NUMBER_OF_SEARCH_ALGORITHMS-1..NUMBER_OF_SEARCH_ALGORITHMS + 1 {
         if i != search_algo {
             let mut challenger = Engine::new(i, eval_algo, time_per_move);
-            let (wins, losses, draws) = simulate_many_battles(&mut challenger, &mut champion, num_battles);
-            print_bar_graph(wins, losses, draws, i, eval_algo);
+            challenger.set_hash_mb(SIMULATOR_HASH_MB);
+            let (stats, pairs) = simulate_many_battles(&mut challenger, &mut champion, num_pairs, openings);
+            print_pairs(&pairs);
+            print_bar_graph(&stats, i, eval_algo);
         }
     }
 }
 
-pub fn print_bar_graph(wins: usize, losses: usize, draws: usize, challenger_search_algo: u8, challenger_eval_algo: u8) {
-    let total = wins + losses + draws;
+pub fn print_bar_graph(stats: &MatchStats, challenger_search_algo: u8, challenger_eval_algo: u8) {
+    let (wins, losses, draws) = (stats.wins, stats.losses, stats.draws);
+    let total = stats.games().max(1);
     let max_bar = 50;
 
     let bar = |count: usize| {
@@ -56,24 +69,151 @@ pub fn print_bar_graph(wins: usize, losses: usize, draws: usize, challenger_sear
     println!("WINS  : {:>3} {}", wins,   bar(wins));
     println!("LOSSES: {:>3} {}", losses, bar(losses));
     println!("DRAWS : {:>3} {}", draws,  bar(draws));
+    println!("{stats}");
+    println!();
+}
+
+/// One line per pair: the opening and the challenger's result with each colour.
+pub fn print_pairs(pairs: &[PairResult]) {
+    let symbol = |outcome| match outcome {
+        Outcome::Win  => "+",
+        Outcome::Loss => "-",
+        Outcome::Draw => "=",
+    };
+    println!();
+    println!("opening  white  black");
+    for pair in pairs {
+        println!("{:>7}  {:>5}  {:>5}", pair.opening, symbol(pair.as_white), symbol(pair.as_black));
+    }
+}
+
+
+/// Plays `challenger` against `champion` in colour-swapped pairs until the SPRT accepts
+/// either hypothesis or `max_pairs` pairs are played, then prints the result.
+pub fn sprt_test(challenger: (u8, u8), champion: (u8, u8), time_per_move: u64, sprt: Sprt, max_pairs: usize) -> (MatchStats, SprtDecision) {
+    let new_engine = |(search_algo, eval_algo)| {
+        let mut engine = Engine::new(search_algo, eval_algo, time_per_move);
+        engine.set_hash_mb(SIMULATOR_HASH_MB);
+        engine
+    };
+    println!("\
+    \n\n\n\n                         SPRT\
+    \n   [search: {}, eval: {}] v. [search: {}, eval: {}]
+   elo0 {}, elo1 {}, alpha {}, beta {}
+--------------------------------------------------------------",
+             challenger.0, challenger.1, champion.0, champion.1, sprt.elo0, sprt.elo1, sprt.alpha, sprt.beta);
+
+    let (stats, decision) = sprt_battles(&new_engine(challenger), &new_engine(champion), sprt, max_pairs);
+    let (lower, upper) = sprt.bounds();
+    println!();
+    println!("{stats}");
+    println!("LLR {:.2} [{lower:.2}, {upper:.2}]: {decision:?}", sprt.llr(&stats));
     println!();
+    (stats, decision)
+}
+
+/// Colour-swapped pairs from random balanced openings, spread over the worker threads,
+/// until the SPRT stops or `max_pairs` is reached. Pairs still running at that point are
+/// not counted.
+pub fn sprt_battles(challenger: &Engine, champion: &Engine, sprt: Sprt, max_pairs: usize) -> (MatchStats, SprtDecision) {
+    let shared = Mutex::new((MatchStats::default(), SprtDecision::Continue));
+    let stop = AtomicBool::new(false);
+
+    let pb = ProgressBar::new(max_pairs as u64).with_style(
+        ProgressStyle::with_template(
+            "{spinner:.green} {elapsed_precise} [{wide_bar:.cyan/blue}] \
+             {pos}/{len} pairs • {msg}",
+        )
+            .unwrap()
+            .progress_chars("=>-"),
+    );
+
+    thread::scope(|scope| {
+        for _ in 0..NUM_THREADS.min(max_pairs as u16) {
+            let (mut challenger, mut champion) = (challenger.clone(), champion.clone());
+            let (shared, stop, pb) = (&shared, &stop, pb.clone());
+            scope.spawn(move || {
+                let mut rng = rng();
+                while !stop.load(Ordering::Relaxed) {
+                    let pair = play_pair(rng.random_range(0..EVEN_FENS.len()), &mut challenger, &mut champion);
+
+                    let mut shared = shared.lock().unwrap();
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let (stats, decision) = &mut *shared;
+                    stats.add_pair(pair.record());
+                    *decision = sprt.decision(stats);
+                    if *decision != SprtDecision::Continue || stats.pairs() >= max_pairs {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                    pb.set_position(stats.pairs() as u64);
+                    pb.set_message(format!("Elo {:+.1}, LLR {:.2}", stats.elo().elo, sprt.llr(stats)));
+                }
+            });
+        }
+    });
+    pb.finish();
+    shared.into_inner().unwrap()
+}
+
+/// Where the openings of a match come from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Openings {
+    Random,             // any EVEN_FENS entry, every time
+    Sequential(usize),  // EVEN_FENS in order from this index, each at most once per lap
 }
 
+/// How a game ended for the challenger.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    /// The result of a game that ended in `status` for the engine playing `color`.
+    fn of(status: Status, color: Color) -> Outcome {
+        match status {
+            Checkmate(winner) if winner == color => Outcome::Win,
+            Checkmate(_) => Outcome::Loss,
+            _ => Outcome::Draw,
+        }
+    }
+}
+
+/// Both games of one opening, first with the challenger as White, then as Black.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PairResult {
+    pub opening:  usize,  // index into EVEN_FENS
+    pub as_white: Outcome,
+    pub as_black: Outcome,
+}
+
+impl PairResult {
+    /// The challenger's (wins, losses, draws) in the pair.
+    pub fn record(&self) -> (usize, usize, usize) {
+        [self.as_white, self.as_black].iter().fold((0, 0, 0), |(w, l, d), outcome| match outcome {
+            Outcome::Win  => (w + 1, l, d),
+            Outcome::Loss => (w, l + 1, d),
+            Outcome::Draw => (w, l, d + 1),
+        })
+    }
+}
+
+/// Plays `num_pairs` openings twice each, with colours swapped, spread over the worker
+/// threads. Returns the challenger's results and every pair, grouped by the thread that
+/// played it; sort them by `opening` for a fixed order.
 pub fn simulate_many_battles(
     challenger: &mut Engine,
     champion: &mut Engine,
-    num_battles: u16,
-) -> (usize, usize, usize) {
-    let mut wins   = 0usize;
-    let mut losses = 0usize;
-    let mut draws  = 0usize;
-
-    const THREAD_BATCH_SIZE: u16 = 10;
-    const NUM_THREADS: u16 = 15;
-
+    num_pairs: u16,
+    openings: Openings,
+) -> (MatchStats, Vec<PairResult>) {
     // ── set up timing + progress ────────────────────────────────────────────────
     let start = Instant::now();
-    let pb = ProgressBar::new(num_battles as u64).with_style(
+    let pb = ProgressBar::new(num_pairs as u64).with_style(
         ProgressStyle::with_template(
             "{spinner:.green} {elapsed_precise} [{wide_bar:.cyan/blue}] \
              {pos}/{len} • ETA {eta_precise}",
@@ -82,101 +222,90 @@ pub fn simulate_many_battles(
             .progress_chars("=>-"),
     );
 
-    // Distribute battles across threads as evenly as possible
-    let per_thread = num_battles / NUM_THREADS;
-    let remainder  = num_battles % NUM_THREADS;
-
-    // Spawn workers
-    let mut handles = Vec::with_capacity(NUM_THREADS as usize);
-    for i in 0..NUM_THREADS {
-        let n = per_thread + if i < remainder { 1 } else { 0 };
-        if n == 0 { continue; }
-
-        // Each thread gets its OWN engine clones and RNG
-        let mut local_challenger = challenger.clone();
-        let mut local_champion   = champion.clone();
-        let pb_clone = pb.clone();
-
-        // Optional: process in small batches inside each thread (keeps your constant used)
-        handles.push(thread::spawn(move || {
-            let mut rng: ThreadRng = rng();
-            let mut w = 0usize;
-            let mut l = 0usize;
-            let mut d = 0usize;
-
-            // do up to THREAD_BATCH_SIZE at a time
-            let mut remaining = n;
-            while remaining > 0 {
-                let step = remaining.min(THREAD_BATCH_SIZE);
-                let (ww, ll, dd) = simulate_fewer_battles(
-                    &mut local_challenger,
-                    &mut local_champion,
-                    &mut rng,
-                    step,
-                );
-                w += ww; l += ll; d += dd;
-                remaining -= step;
-                pb_clone.inc(step as u64);
-            }
+    // Distribute pairs across threads as evenly as possible
+    let per_thread = num_pairs / NUM_THREADS;
+    let remainder  = num_pairs % NUM_THREADS;
+    let next_opening = AtomicUsize::new(0);
 
-            (w, l, d)
-        }));
-    }
+    let pairs: Vec<PairResult> = thread::scope(|scope| {
+        let mut handles = Vec::with_capacity(NUM_THREADS as usize);
+        for i in 0..NUM_THREADS {
+            let n = per_thread + if i < remainder { 1 } else { 0 };
+            if n == 0 { continue; }
 
-    // Collect results
-    for h in handles {
-        let (w, l, d) = h.join().expect("thread panicked");
-        wins += w; losses += l; draws += d;
-    }
+            // Each thread gets its OWN engine clones and RNG
+            // (clones keep the hash size, but start with empty tables)
+            let mut local_challenger = challenger.clone();
+            let mut local_champion   = champion.clone();
+            let (pb, next_opening) = (pb.clone(), &next_opening);
+
+            handles.push(scope.spawn(move || {
+                let mut rng = rng();
+                (0..n).map(|_| {
+                    let opening = match openings {
+                        Openings::Random => rng.random_range(0..EVEN_FENS.len()),
+                        Openings::Sequential(first) => (first + next_opening.fetch_add(1, Ordering::Relaxed)) % EVEN_FENS.len(),
+                    };
+                    let pair = play_pair(opening, &mut local_challenger, &mut local_champion);
+                    pb.inc(1);
+                    pair
+                }).collect::<Vec<_>>()
+            }));
+        }
+        handles.into_iter().flat_map(|h| h.join().expect("thread panicked")).collect()
+    });
 
+    let mut stats = MatchStats::default();
+    pairs.iter().for_each(|pair| stats.add_pair(pair.record()));
     pb.finish_with_message(format!("Done in {:?}", start.elapsed()));
-    (wins, losses, draws)
+    (stats, pairs)
 }
 
-fn simulate_fewer_battles(
-    challenger: &mut Engine,
-    champion: &mut Engine,
-    rng: &mut ThreadRng,
-    num_battles: u16,
-) -> (usize, usize, usize) {
-    let mut wins   = 0usize;
-    let mut losses = 0usize;
-    let mut draws  = 0usize;
-
-    for _ in 0..num_battles {
-        let result = battle(challenger, champion, rng);
-        match result {
-            Checkmate(Color::White) => wins += 1,
-            Checkmate(Color::Black) => losses += 1,
-            Draw => draws += 1,
-            _ => {}
-        }
+/// Plays opening `opening` twice, `first` taking White in the first game and Black in the
+/// second.
+pub(crate) fn play_pair(opening: usize, first: &mut Engine, second: &mut Engine) -> PairResult {
+    let fen = EVEN_FENS[opening];
+    PairResult {
+        opening,
+        as_white: Outcome::of(play_game(fen, first, second), Color::White),
+        as_black: Outcome::of(play_game(fen, second, first), Color::Black),
     }
-
-    (wins, losses, draws)
 }
 
+/// One game from `fen`, each engine starting with an empty hash and a full clock.
+fn play_game(fen: &str, white: &mut Engine, black: &mut Engine) -> Status {
+    let mut position = Position::load_position_from_fen(fen);  // or Position::from_id(position_id)
 
- fn battle<R: Rng>(challenger: &mut Engine, champion: &mut Engine, rng: &mut R) -> Status {
-     // champion moves on white
-    let position_id = rng.random_range(0..5000);
+    // nothing carries over from the previous game
+    white.clear_hash();
+    black.clear_hash();
 
-    let fen = EVEN_FENS[position_id];
-    //println!("\n\n\n\n FEN: {fen}");
+    // engines with a clock play the whole game on it; it is restored for the next game
+    let (white_clock, black_clock) = (white.clock(), black.clock());
+    let result = play_out(&mut position, white, black);
+    white.set_clock(white_clock);
+    black.set_clock(black_clock);
+    result
+}
 
-    let mut position = Position::load_position_from_fen(fen);  // or Position::from_id(position_id)
+fn play_out(position: &mut Position, white: &mut Engine, black: &mut Engine) -> Status {
     let mut moves = 0;
-    while !all_moves(&position).is_empty() {
+    while !all_moves(position).is_empty() {
         if moves > 200 || position.is_three_fold_repetition() || position.half_move_over_ninety_nine() {
             return Draw
         }
         moves += 1;
-        let mov = if position.side_to_move() == Color::White {
-            champion.pick(&mut position)
-        } else {
-            challenger.pick(&mut position)
+        let side   = position.side_to_move();
+        let engine = if side == Color::White { &mut *white } else { &mut *black };
 
-        };
+        let started = Instant::now();
+        let mov = engine.pick(position);
+        if let Some(mut clock) = engine.clock() {
+            if !clock.punch(started.elapsed().as_millis() as u64) {
+                return Checkmate(!side);  // lost on time
+            }
+            engine.set_clock(Some(clock));
+        }
         position.do_move(mov);
         // safe_move_or_debug(&mut position, mov, fen).await;
         // println!("Move #{moves}: {mov}");
@@ -1,2 +1,6 @@
 pub mod engine_battle_simulator;
+pub mod datagen;
+pub mod spsa;
+pub mod stats;
+pub mod tournament;
 mod even_fens;
\ No newline at end of file
@@ -0,0 +1,187 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Instant;
+use indicatif::{ProgressBar, ProgressStyle};
+use rand::{rng, Rng};
+use crate::engines::engine_manager::{Engine, SearchLimits, NUMBER_OF_EVAL_ALGORITHMS};
+use crate::engines::search_params::{SearchParams, PARAM_SPECS};
+use crate::simulator::engine_battle_simulator::play_pair;
+use crate::simulator::even_fens::EVEN_FENS;
+
+/// The only search that reads `SearchParams`.
+const SPSA_SEARCH: u8 = 29;
+// like the simulator: every thread has two engines of its own, so keep the tables small
+const SPSA_HASH_MB: usize = 16;
+// the usual SPSA decay exponents and stability constant (as a share of the iterations)
+const ALPHA: f64 = 0.602;
+const GAMMA: f64 = 0.101;
+const STABILITY: f64 = 0.1;
+
+
+/// SPSA over the search parameters: every iteration plays game pairs between two engines
+/// whose parameters are all pushed the same distance in random opposite directions, then
+/// moves the parameters towards the side that scored better. Perturbations and steps shrink
+/// over the run, ending at each parameter's `ParamSpec::step`.
+pub struct Spsa {
+    pub iterations:    u32,
+    pub pairs:         u32,          // game pairs per iteration
+    pub threads:       usize,
+    pub time_ms:       u64,          // per move
+    pub nodes:         Option<u64>,  // per move, instead of the time
+    pub eval_algo:     u8,
+    pub learning_rate: f64,          // step at the end of the run, relative to `ParamSpec::step`
+}
+
+impl Default for Spsa {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spsa {
+    pub fn new() -> Spsa {
+        Spsa {
+            iterations:    10_000,
+            pairs:         8,
+            threads:       thread::available_parallelism().map_or(1, |n| n.get()),
+            time_ms:       10,
+            nodes:         None,
+            eval_algo:     NUMBER_OF_EVAL_ALGORITHMS,
+            learning_rate: 0.002,
+        }
+    }
+
+    /// Runs the remaining iterations, appending one CSV line per iteration to `log`: the
+    /// iteration, every parameter after it and the perturbed-up engine's wins, losses and
+    /// draws. An existing log is resumed from its last line. Returns the tuned parameters.
+    pub fn run(&self, log: &Path) -> io::Result<SearchParams> {
+        let (done, mut theta) = resume(log)?;
+        let mut file = OpenOptions::new().append(true).open(log)?;
+
+        let n = self.iterations as f64;
+        let big_a = STABILITY * n;
+        // scaled so that the perturbation ends at `step` and the update at `learning_rate * step^2`
+        let c: Vec<f64> = PARAM_SPECS.iter().map(|spec| spec.step as f64 * n.powf(GAMMA)).collect();
+        let a: Vec<f64> = PARAM_SPECS.iter()
+            .map(|spec| self.learning_rate * (spec.step as f64).powi(2) * (big_a + n).powf(ALPHA))
+            .collect();
+
+        let start = Instant::now();
+        let pb = ProgressBar::new(self.iterations as u64).with_style(
+            ProgressStyle::with_template(
+                "{spinner:.green} {elapsed_precise} [{wide_bar:.cyan/blue}] \
+                 {pos}/{len} • ETA {eta_precise}",
+            )
+                .unwrap()
+                .progress_chars("=>-"),
+        );
+        pb.set_position(done as u64);
+
+        let mut rng = rng();
+        for k in done + 1..=self.iterations {
+            let c_k: Vec<f64> = c.iter().map(|c| c / (k as f64).powf(GAMMA)).collect();
+            let a_k: Vec<f64> = a.iter().map(|a| a / (big_a + k as f64).powf(ALPHA)).collect();
+            let delta: Vec<f64> = (0..PARAM_SPECS.len()).map(|_| if rng.random_bool(0.5) { 1.0 } else { -1.0 }).collect();
+
+            let shifted = |sign: f64| SearchParams::from_values(std::array::from_fn(|i| {
+                (theta[i] + sign * c_k[i] * delta[i]).round() as i32
+            }));
+            let (wins, losses, draws) = self.play(shifted(1.0), shifted(-1.0));
+
+            let result = wins as f64 - losses as f64;
+            for (i, spec) in PARAM_SPECS.iter().enumerate() {
+                theta[i] = (theta[i] + a_k[i] * result / (c_k[i] * delta[i])).clamp(spec.min as f64, spec.max as f64);
+            }
+
+            let values: String = theta.iter().map(|v| format!(",{v:.3}")).collect();
+            writeln!(file, "{k}{values},{wins},{losses},{draws}")?;
+            file.flush()?;
+            pb.inc(1);
+        }
+        pb.finish_with_message(format!("Done in {:?}", start.elapsed()));
+
+        Ok(SearchParams::from_values(std::array::from_fn(|i| theta[i].round() as i32)))
+    }
+
+    /// `pairs` game pairs from random balanced openings; returns `plus`'s (wins, losses, draws).
+    fn play(&self, plus: SearchParams, minus: SearchParams) -> (usize, usize, usize) {
+        let engine = |params| {
+            let mut engine = Engine::new(SPSA_SEARCH, self.eval_algo, self.time_ms);
+            engine.set_hash_mb(SPSA_HASH_MB);
+            engine.set_search_params(params);
+            if let Some(nodes) = self.nodes {
+                engine.set_limits(SearchLimits { nodes: Some(nodes), infinite: true, ..SearchLimits::default() });
+            }
+            engine
+        };
+
+        // distribute the pairs across threads as evenly as possible
+        let threads = (self.threads.max(1) as u32).min(self.pairs.max(1));
+        let per_thread = self.pairs / threads;
+        let remainder  = self.pairs % threads;
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads).map(|i| {
+                let n = per_thread + if i < remainder { 1 } else { 0 };
+                let (mut plus, mut minus) = (engine(plus), engine(minus));
+                scope.spawn(move || {
+                    let mut rng = rng();
+                    let mut record = (0, 0, 0);
+                    for _ in 0..n {
+                        let opening = rng.random_range(0..EVEN_FENS.len());
+                        let (w, l, d) = play_pair(opening, &mut plus, &mut minus).record();
+                        record = (record.0 + w, record.1 + l, record.2 + d);
+                    }
+                    record
+                })
+            }).collect();
+            handles.into_iter()
+                .map(|h| h.join().expect("thread panicked"))
+                .fold((0, 0, 0), |a, b| (a.0 + b.0, a.1 + b.1, a.2 + b.2))
+        })
+    }
+}
+
+fn header() -> String {
+    let names: String = PARAM_SPECS.iter().map(|spec| format!(",{}", spec.name)).collect();
+    format!("iteration{names},wins,losses,draws")
+}
+
+/// Starts a new log, or reads where an existing one got to: the last iteration and the
+/// parameters after it. A line cut off by an interrupted run is dropped.
+fn resume(log: &Path) -> io::Result<(u32, Vec<f64>)> {
+    let defaults = SearchParams::default().values().iter().map(|&v| v as f64).collect();
+    if !log.exists() {
+        let mut file = File::create(log)?;
+        writeln!(file, "{}", header())?;
+        return Ok((0, defaults));
+    }
+
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {message}", log.display()));
+    let mut reader = BufReader::new(File::open(log)?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    if line.trim_end() != header() {
+        return Err(invalid("not a log of these parameters".to_string()));
+    }
+
+    let mut kept = line.len() as u64;
+    let mut last = (0, defaults);
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || !line.ends_with('\n') {
+            break;
+        }
+        let fields: Vec<&str> = line.trim_end().split(',').collect();
+        let iteration = fields[0].parse().map_err(|_| invalid(format!("bad line '{}'", line.trim_end())))?;
+        let theta: Option<Vec<f64>> = fields.get(1..=PARAM_SPECS.len())
+            .map(|values| values.iter().map(|v| v.parse().ok()).collect())
+            .unwrap_or(None);
+        let theta = theta.ok_or_else(|| invalid(format!("bad line '{}'", line.trim_end())))?;
+        last = (iteration, theta);
+        kept += line.len() as u64;
+    }
+    OpenOptions::new().write(true).open(log)?.set_len(kept)?;
+    Ok(last)
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+use statrs::distribution::{ContinuousCDF, Normal};
+
+/// Two-sided 95% confidence.
+const CONFIDENCE: f64 = 0.95;
+
+
+/// Expected score of the stronger side of an `elo` difference (logistic model).
+pub fn score_from_elo(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Elo difference that makes `score` the expected score; infinite at 0 and 1.
+pub fn elo_from_score(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+fn normal() -> Normal {
+    Normal::new(0.0, 1.0).expect("valid parameters")
+}
+
+
+/// An Elo difference with its 95% confidence interval.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Elo {
+    pub elo:   f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl fmt::Display for Elo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:+.1} [{:+.1}, {:+.1}]", self.elo, self.lower, self.upper)
+    }
+}
+
+
+/// Results of one engine against another. Games played in colour-swapped pairs also land
+/// in `pentanomial`: how many pairs the engine scored 0, ½, 1, 1½ and 2 points in. Pairs
+/// share their opening, so their scores vary less than single games, and the statistics
+/// use them whenever there are any.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MatchStats {
+    pub wins:        usize,
+    pub losses:      usize,
+    pub draws:       usize,
+    pub pentanomial: [usize; 5],
+}
+
+impl MatchStats {
+    /// Single games only, with no pairs.
+    pub fn from_games(wins: usize, losses: usize, draws: usize) -> MatchStats {
+        MatchStats { wins, losses, draws, pentanomial: [0; 5] }
+    }
+
+    /// Adds the (wins, losses, draws) of one game pair.
+    pub fn add_pair(&mut self, (wins, losses, draws): (usize, usize, usize)) {
+        debug_assert_eq!(wins + losses + draws, 2);
+        self.wins   += wins;
+        self.losses += losses;
+        self.draws  += draws;
+        self.pentanomial[2 * wins + draws] += 1;
+    }
+
+    pub fn games(&self) -> usize {
+        self.wins + self.losses + self.draws
+    }
+
+    pub fn pairs(&self) -> usize {
+        self.pentanomial.iter().sum()
+    }
+
+    /// Points per game.
+    pub fn score(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games().max(1) as f64
+    }
+
+    /// Mean and variance of the score per game, from the pairs if there are any, and how
+    /// many pairs or games they are over. `prior` is added to every outcome's count.
+    fn score_distribution(&self, prior: f64) -> (f64, f64, usize) {
+        let (outcomes, counts): (Vec<f64>, Vec<usize>) = if self.pairs() > 0 {
+            ((0..5).map(|i| i as f64 / 4.0).collect(), self.pentanomial.to_vec())
+        } else {
+            (vec![1.0, 0.0, 0.5], vec![self.wins, self.losses, self.draws])
+        };
+        let n: usize = counts.iter().sum();
+        if n == 0 {
+            return (0.5, 0.0, 0);
+        }
+        let counts: Vec<f64> = counts.iter().map(|&c| c as f64 + prior).collect();
+        let total: f64 = counts.iter().sum();
+        let mean = outcomes.iter().zip(&counts).map(|(x, c)| x * c).sum::<f64>() / total;
+        let variance = outcomes.iter().zip(&counts).map(|(x, c)| (x - mean).powi(2) * c).sum::<f64>() / total;
+        (mean, variance, n)
+    }
+
+    /// Elo difference and its 95% confidence interval.
+    pub fn elo(&self) -> Elo {
+        let (mean, variance, n) = self.score_distribution(0.0);
+        let z = normal().inverse_cdf(0.5 + CONFIDENCE / 2.0);
+        let margin = z * (variance / n.max(1) as f64).sqrt();
+        Elo {
+            elo:   elo_from_score(mean),
+            lower: elo_from_score((mean - margin).max(0.0)),
+            upper: elo_from_score((mean + margin).min(1.0)),
+        }
+    }
+
+    /// Likelihood of superiority: how sure we can be that the engine is the stronger one.
+    /// Draws say nothing either way.
+    pub fn los(&self) -> f64 {
+        let decisive = (self.wins + self.losses) as f64;
+        if decisive == 0.0 {
+            return 0.5;
+        }
+        normal().cdf((self.wins as f64 - self.losses as f64) / decisive.sqrt())
+    }
+}
+
+impl fmt::Display for MatchStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Games: {} (+{} -{} ={}), score {:.1}%, Elo {}, LOS {:.1}%",
+               self.games(), self.wins, self.losses, self.draws, 100.0 * self.score(), self.elo(), 100.0 * self.los())?;
+        if self.pairs() > 0 {
+            let [a, b, c, d, e] = self.pentanomial;
+            write!(f, "\nPairs: {} [{a}, {b}, {c}, {d}, {e}]", self.pairs())?;
+        }
+        Ok(())
+    }
+}
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SprtDecision {
+    AcceptH0,  // the difference is elo0 (or less): stop
+    AcceptH1,  // the difference is elo1 (or more): stop
+    Continue,
+}
+
+/// Sequential probability ratio test of H0: Elo = `elo0` against H1: Elo = `elo1`, with
+/// false positive rate `alpha` and false negative rate `beta`. The log-likelihood ratio is
+/// the usual normal approximation over game pairs (over games when there are no pairs).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sprt {
+    pub elo0:  f64,
+    pub elo1:  f64,
+    pub alpha: f64,
+    pub beta:  f64,
+}
+
+impl Default for Sprt {
+    fn default() -> Self {
+        Sprt { elo0: 0.0, elo1: 5.0, alpha: 0.05, beta: 0.05 }
+    }
+}
+
+impl Sprt {
+    /// The (lower, upper) LLR bounds: H0 is accepted below the first, H1 above the second.
+    pub fn bounds(&self) -> (f64, f64) {
+        ((self.beta / (1.0 - self.alpha)).ln(), ((1.0 - self.beta) / self.alpha).ln())
+    }
+
+    pub fn llr(&self, stats: &MatchStats) -> f64 {
+        // a small prior keeps the variance above zero while every pair has ended alike
+        let (mean, variance, n) = stats.score_distribution(1e-3);
+        if n == 0 {
+            return 0.0;
+        }
+        let (s0, s1) = (score_from_elo(self.elo0), score_from_elo(self.elo1));
+        n as f64 * (s1 - s0) * (2.0 * mean - s0 - s1) / (2.0 * variance)
+    }
+
+    pub fn decision(&self, stats: &MatchStats) -> SprtDecision {
+        let llr = self.llr(stats);
+        let (lower, upper) = self.bounds();
+        if llr <= lower {
+            SprtDecision::AcceptH0
+        } else if llr >= upper {
+            SprtDecision::AcceptH1
+        } else {
+            SprtDecision::Continue
+        }
+    }
+}
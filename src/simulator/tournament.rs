@@ -0,0 +1,299 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Instant;
+use indicatif::{ProgressBar, ProgressStyle};
+use rand::{rng, Rng};
+use crate::engines::engine_manager::{Engine, Eval, Search, SearchLimits};
+use crate::simulator::engine_battle_simulator::{play_pair, Openings};
+use crate::simulator::even_fens::EVEN_FENS;
+use crate::simulator::stats::MatchStats;
+
+// every thread keeps the two engines of the pairing it is playing, so keep the tables small
+const TOURNAMENT_HASH_MB: usize = 16;
+// virtual draws added to every pairing that was played, so that an engine that won (or
+// lost) every game still gets a finite rating
+const PRIOR_DRAWS: f64 = 2.0;
+const Z_95: f64 = 1.96;
+
+
+/// One participant: search and eval algorithm and time per move.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EngineSpec {
+    pub search:  u8,
+    pub eval:    u8,
+    pub time_ms: u64,
+}
+
+impl EngineSpec {
+    /// Every search algorithm with the same eval and time, to rank the searches at once.
+    pub fn all_searches(eval: u8, time_ms: u64) -> Vec<EngineSpec> {
+        (1..=u8::MAX)
+            .take_while(|&search| Search::try_from(search).is_ok())
+            .map(|search| EngineSpec { search, eval, time_ms })
+            .collect()
+    }
+
+    fn engine(&self, nodes: Option<u64>) -> Engine {
+        let mut engine = Engine::new(self.search, self.eval, self.time_ms);
+        engine.set_hash_mb(TOURNAMENT_HASH_MB);
+        if let Some(nodes) = nodes {
+            engine.set_limits(SearchLimits { nodes: Some(nodes), infinite: true, ..SearchLimits::default() });
+        }
+        engine
+    }
+}
+
+impl fmt::Display for EngineSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "s{} e{} {}ms", self.search, self.eval, self.time_ms)
+    }
+}
+
+/// `search:eval:time_ms`, e.g. `29:5:10`.
+impl FromStr for EngineSpec {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let bad = || format!("bad engine '{text}', expected search:eval:time_ms");
+        let [search, eval, time_ms] = text.split(':').collect::<Vec<_>>()[..] else {
+            return Err(bad());
+        };
+        let spec = EngineSpec {
+            search:  search.parse().map_err(|_| bad())?,
+            eval:    eval.parse().map_err(|_| bad())?,
+            time_ms: time_ms.parse().map_err(|_| bad())?,
+        };
+        if Search::try_from(spec.search).is_err() || Eval::try_from(spec.eval).is_err() {
+            return Err(format!("no such engine '{text}'"));
+        }
+        Ok(spec)
+    }
+}
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    RoundRobin,  // everyone against everyone
+    Gauntlet,    // the first engine against each of the others
+}
+
+/// A tournament between any set of engines. Every pairing plays `games` games (rounded up
+/// to whole pairs) as colour-swapped pairs; with `Openings::Sequential` every pairing gets
+/// the same openings.
+pub struct Tournament {
+    pub engines:  Vec<EngineSpec>,
+    pub format:   Format,
+    pub games:    u32,          // per pairing
+    pub openings: Openings,
+    pub threads:  usize,
+    pub nodes:    Option<u64>,  // per move, instead of the time; every search but random counts them
+}
+
+impl Tournament {
+    pub fn new(engines: Vec<EngineSpec>) -> Tournament {
+        Tournament {
+            engines,
+            format:   Format::RoundRobin,
+            games:    100,
+            openings: Openings::Sequential(0),
+            threads:  thread::available_parallelism().map_or(1, |n| n.get()),
+            nodes:    None,
+        }
+    }
+
+    /// Index pairs of the engines that meet.
+    pub fn pairings(&self) -> Vec<(usize, usize)> {
+        let n = self.engines.len();
+        match self.format {
+            Format::RoundRobin => (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))).collect(),
+            Format::Gauntlet   => (1..n).map(|j| (0, j)).collect(),
+        }
+    }
+
+    pub fn run(&self) -> Standings {
+        let pairs = self.games.div_ceil(2) as usize;
+        let mut rng = rng();
+        // (first, second, opening) for every pair of games
+        let jobs: Vec<(usize, usize, usize)> = self.pairings().into_iter()
+            .flat_map(|(i, j)| (0..pairs).map(move |k| (i, j, k)))
+            .map(|(i, j, k)| (i, j, match self.openings {
+                Openings::Random => rng.random_range(0..EVEN_FENS.len()),
+                Openings::Sequential(first) => (first + k) % EVEN_FENS.len(),
+            }))
+            .collect();
+
+        let start = Instant::now();
+        let pb = ProgressBar::new(jobs.len() as u64).with_style(
+            ProgressStyle::with_template(
+                "{spinner:.green} {elapsed_precise} [{wide_bar:.cyan/blue}] \
+                 {pos}/{len} • ETA {eta_precise}",
+            )
+                .unwrap()
+                .progress_chars("=>-"),
+        );
+
+        let n = self.engines.len();
+        let results = Mutex::new(vec![vec![MatchStats::default(); n]; n]);
+        let next_job = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..self.threads.max(1).min(jobs.len()) {
+                let (jobs, results, next_job, pb) = (&jobs, &results, &next_job, pb.clone());
+                scope.spawn(move || {
+                    // jobs come pairing by pairing: keep the two engines while the pairing
+                    // lasts, rather than a hash table for every participant in every thread
+                    let mut engines: Vec<Option<Engine>> = (0..n).map(|_| None).collect();
+                    while let Some(&(i, j, opening)) = jobs.get(next_job.fetch_add(1, Ordering::Relaxed)) {
+                        for (k, engine) in engines.iter_mut().enumerate() {
+                            if k != i && k != j {
+                                *engine = None;
+                            } else if engine.is_none() {
+                                *engine = Some(self.engines[k].engine(self.nodes));
+                            }
+                        }
+                        let (first, second) = two_mut(&mut engines, i, j);
+                        let (w, l, d) = play_pair(opening, first.as_mut().unwrap(), second.as_mut().unwrap()).record();
+
+                        let mut results = results.lock().unwrap();
+                        results[i][j].add_pair((w, l, d));
+                        results[j][i].add_pair((l, w, d));
+                        pb.inc(1);
+                    }
+                });
+            }
+        });
+        pb.finish_with_message(format!("Done in {:?}", start.elapsed()));
+
+        Standings { engines: self.engines.clone(), results: results.into_inner().unwrap() }
+    }
+}
+
+fn two_mut<T>(items: &mut [T], i: usize, j: usize) -> (&mut T, &mut T) {
+    assert_ne!(i, j);
+    if i < j {
+        let (left, right) = items.split_at_mut(j);
+        (&mut left[i], &mut right[0])
+    } else {
+        let (left, right) = items.split_at_mut(i);
+        (&mut right[0], &mut left[j])
+    }
+}
+
+
+/// One line of the rating list.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rating {
+    pub engine: usize,  // index into `Standings::engines`
+    pub elo:    f64,    // relative to the field's average
+    pub error:  f64,    // 95% margin
+    pub games:  usize,
+    pub score:  f64,    // points per game
+}
+
+/// Everything a tournament played: `results[i][j]` is engine `i`'s record against `j`.
+pub struct Standings {
+    pub engines: Vec<EngineSpec>,
+    pub results: Vec<Vec<MatchStats>>,
+}
+
+impl Standings {
+    /// Maximum likelihood Bradley-Terry ratings (as BayesElo and Ordo fit them, with a
+    /// draw counting half a win for each side), best first. The margins only account for
+    /// each engine's own games, not for the uncertainty of its opponents' ratings.
+    pub fn ratings(&self) -> Vec<Rating> {
+        let n = self.engines.len();
+        let played = |i: usize, j: usize| self.results[i][j].games() as f64;
+        let games = |i: usize, j: usize| if played(i, j) > 0.0 { played(i, j) + PRIOR_DRAWS } else { 0.0 };
+        let points = |i: usize| (0..n)
+            .filter(|&j| played(i, j) > 0.0)
+            .map(|j| {
+                let stats = &self.results[i][j];
+                stats.wins as f64 + (stats.draws as f64 + PRIOR_DRAWS) / 2.0
+            })
+            .sum::<f64>();
+
+        // minorization-maximization on the strengths gamma = 10^(elo / 400)
+        let mut gamma = vec![1.0; n];
+        for _ in 0..10_000 {
+            let next: Vec<f64> = (0..n).map(|i| {
+                let denominator: f64 = (0..n).map(|j| games(i, j) / (gamma[i] + gamma[j])).sum();
+                if denominator > 0.0 { points(i) / denominator } else { 1.0 }
+            }).collect();
+            // anchored on the geometric mean
+            let mean = next.iter().map(|g| g.ln()).sum::<f64>() / n as f64;
+            let next: Vec<f64> = next.iter().map(|g| (g.ln() - mean).exp()).collect();
+            let change = next.iter().zip(&gamma).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+            gamma = next;
+            if change < 1e-10 {
+                break;
+            }
+        }
+
+        let elo_per_ln = 400.0 / 10f64.ln();
+        let mut ratings: Vec<Rating> = (0..n).map(|i| {
+            let expected = |j: usize| gamma[i] / (gamma[i] + gamma[j]);
+            let information: f64 = (0..n).map(|j| played(i, j) * expected(j) * (1.0 - expected(j))).sum();
+            let total = MatchStats {
+                wins:   (0..n).map(|j| self.results[i][j].wins).sum(),
+                losses: (0..n).map(|j| self.results[i][j].losses).sum(),
+                draws:  (0..n).map(|j| self.results[i][j].draws).sum(),
+                ..MatchStats::default()
+            };
+            Rating {
+                engine: i,
+                elo:    elo_per_ln * gamma[i].ln(),
+                error:  if information > 0.0 { Z_95 * elo_per_ln / information.sqrt() } else { f64::INFINITY },
+                games:  total.games(),
+                score:  total.score(),
+            }
+        }).collect();
+        ratings.sort_by(|a, b| b.elo.total_cmp(&a.elo));
+        ratings
+    }
+
+    /// The rating list, best first.
+    pub fn rating_list(&self) -> String {
+        let mut text = format!("{:>3}  {:<16} {:>6} {:>5} {:>6} {:>6}\n", "#", "engine", "elo", "+/-", "games", "score");
+        for (rank, rating) in self.ratings().iter().enumerate() {
+            text += &format!("{:>3}  {:<16} {:>+6.0} {:>5.0} {:>6} {:>5.1}%\n",
+                             rank + 1, self.engines[rating.engine].to_string(), rating.elo, rating.error,
+                             rating.games, 100.0 * rating.score);
+        }
+        text
+    }
+
+    /// Points scored by every engine (rows, best first) against every other (columns, in
+    /// the same order).
+    pub fn crosstable(&self) -> String {
+        let order: Vec<usize> = self.ratings().iter().map(|rating| rating.engine).collect();
+        let mut text = format!("{:>3}  {:<16}", "#", "engine");
+        for rank in 1..=order.len() {
+            text += &format!(" {rank:>9}");
+        }
+        text.push('\n');
+        for (rank, &i) in order.iter().enumerate() {
+            text += &format!("{:>3}  {:<16}", rank + 1, self.engines[i].to_string());
+            for &j in &order {
+                let stats = &self.results[i][j];
+                let cell = if i == j {
+                    "·".to_string()
+                } else if stats.games() == 0 {
+                    String::new()
+                } else {
+                    format!("{}/{}", stats.wins as f64 + stats.draws as f64 / 2.0, stats.games())
+                };
+                text += &format!(" {cell:>9}");
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\n{}", self.crosstable(), self.rating_list())
+    }
+}
@@ -15,6 +15,7 @@ mod between_exclusive;
 mod between_inclusive;
 mod line_bb;
 pub mod zobrist;
+pub mod polyglot;
 
 pub use line_bb::LINE_BB;
 pub use between_inclusive::BETWEEN_INCLUSIVE;
@@ -0,0 +1,313 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use crate::color::Color;
+use crate::engines::bitbase;
+use crate::engines::evaluate::{king_safety, mobility, pawns};
+use crate::eval::{mirror, Terms, BISHOP_PAIR_INDEX, CONNECTED_INDEX, DOUBLED_INDEX, EG_PARAMS, FREE_PATH_INDEX, KING_ATTACK_INDEX, KING_PROXIMITY_INDEX, MATERIAL_INDEX, MOBILITY_INDEX, NUM_EVAL_PARAMS, PASSED_INDEX, PST_INDEX, SHIELD_INDEX, SIMPLIFY_INDEX, TRAPPED_INDEX};
+use crate::eval_params::EVAL_PARAMS;
+use crate::piece::Piece;
+use crate::position::Position;
+use crate::simulator::datagen::{Record, RECORD_SIZE};
+
+const PIECE_NAMES: [&str; 6] = ["pawn", "knight", "bishop", "rook", "queen", "king"];
+/// Non-pawn pieces on the board at the start; see `Position::evaluate_2`.
+const MAX_NONPAWN_START: i32 = 14;
+/// The parameters after the piece-square tables, as eval_params.rs comments them.
+const GROUPS: [(&str, usize); 11] = [
+    ("bonus per piece traded off while ahead", SIMPLIFY_INDEX),
+    ("doubled, isolated, backward pawn penalties", DOUBLED_INDEX),
+    ("connected pawn by rank", CONNECTED_INDEX),
+    ("passed pawn by rank", PASSED_INDEX),
+    ("passed pawn with a free path by rank", FREE_PATH_INDEX),
+    ("passed pawn king distance by rank", KING_PROXIMITY_INDEX),
+    ("king-zone attacks by knight, bishop, rook, queen", KING_ATTACK_INDEX),
+    ("king shield advanced, missing; semi-open, open file at the king", SHIELD_INDEX),
+    ("mobility per safe square: knight, bishop, rook, queen", MOBILITY_INDEX),
+    ("bishop pair; rook on an open, semi-open file, on the seventh; knight outpost", BISHOP_PAIR_INDEX),
+    ("trapped bishop, rook penalties", TRAPPED_INDEX),
+];
+
+
+/// One training position: how often each middlegame parameter (and so its endgame twin)
+/// counts in its eval, White minus Black.
+struct Entry {
+    start:  usize,  // into `Tuner::features`
+    len:    usize,
+    phase:  i32,    // middlegame weight, 0..=24
+    result: f64,    // 1.0 White won, 0.5 draw, 0.0 Black won
+}
+
+/// Texel tuning of `EVAL_PARAMS`, every weight of the hand-written eval the engine plays
+/// (`Eval::WithMobility`, which the NNUE eval falls back to): material, piece-square tables,
+/// the simplification bonus, pawn structure, king safety and mobility. Fits the K of the
+/// win-probability sigmoid `1 / (1 + 10^(-K * eval / 400))` to the game results, then
+/// minimises the mean squared error with Adam.
+pub struct Tuner {
+    pub epochs:        usize,
+    pub learning_rate: f64,  // centipawns per step, roughly
+    params:   Vec<f64>,
+    features: Vec<(u16, f32)>,
+    entries:  Vec<Entry>,
+}
+
+impl Default for Tuner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tuner {
+    pub fn new() -> Tuner {
+        Tuner {
+            epochs:        1_000,
+            learning_rate: 1.0,
+            params:        EVAL_PARAMS.iter().map(|&p| p as f64).collect(),
+            features:      Vec::new(),
+            entries:       Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds a position and the result of its game for White. Positions the bitbases already
+    /// know are left out, since the eval never sees them. Returns whether it was added.
+    pub fn add(&mut self, pos: &Position, result: f64) -> bool {
+        if bitbase::evaluate(pos).is_some() {
+            return false;
+        }
+        let start = self.features.len();
+        self.features.extend(coefficients(pos).iter().enumerate()
+            .filter(|&(_, &count)| count != 0.0)
+            .map(|(i, &count)| (i as u16, count)));
+        self.entries.push(Entry { start, len: self.features.len() - start, phase: pos.mg_phase(), result });
+        true
+    }
+
+    /// Loads labelled positions: `.bin` files from the data generator, or text with one
+    /// position per line, either the generator's `<fen> | <score> | <result>` or a FEN
+    /// followed by a result such as `[0.5]`, `1-0` or `c9 "1/2-1/2";`. Returns how many
+    /// positions were added.
+    pub fn load(&mut self, path: &Path) -> io::Result<usize> {
+        let before = self.len();
+        let invalid = |line: usize| io::Error::new(io::ErrorKind::InvalidData, format!("line {line}: no position and result"));
+
+        if path.extension().is_some_and(|ext| ext == "bin") {
+            for chunk in fs::read(path)?.chunks_exact(RECORD_SIZE) {
+                let record = Record::from_bytes(chunk.try_into().unwrap());
+                let pos = Position::from_fen(&record.fen).map_err(|_| invalid(0))?;
+                self.add(&pos, record.result as f64);
+            }
+        } else {
+            for (i, line) in fs::read_to_string(path)?.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let (fen, result) = parse_line(line).ok_or_else(|| invalid(i + 1))?;
+                let pos = Position::from_fen(&fen).map_err(|_| invalid(i + 1))?;
+                self.add(&pos, result);
+            }
+        }
+        Ok(self.len() - before)
+    }
+
+    /// The eval of a position with the current parameters, White's point of view.
+    pub fn evaluate(&self, pos: &Position) -> f64 {
+        let phase = pos.mg_phase() as f64;
+        coefficients(pos).iter().enumerate()
+            .map(|(i, &count)| count as f64 * (self.params[i] * phase + self.params[EG_PARAMS + i] * (24.0 - phase)) / 24.0)
+            .sum()
+    }
+
+    /// Mean squared difference between results and predicted scores.
+    pub fn error(&self, k: f64) -> f64 {
+        let total: f64 = self.entries.iter().map(|entry| (entry.result - sigmoid(k, self.eval(entry))).powi(2)).sum();
+        total / self.len().max(1) as f64
+    }
+
+    /// The K that best fits the current eval to the results (golden-section search).
+    pub fn fit_k(&self) -> f64 {
+        const RATIO: f64 = 0.618_033_988_749_895;
+        let (mut lo, mut hi) = (0.0, 4.0);
+        while hi - lo > 1e-4 {
+            let a = hi - RATIO * (hi - lo);
+            let b = lo + RATIO * (hi - lo);
+            if self.error(a) < self.error(b) { hi = b } else { lo = a }
+        }
+        (lo + hi) / 2.0
+    }
+
+    /// Runs `epochs` full-batch Adam steps at the given K, calling `report` with the epoch
+    /// and the error after each one.
+    pub fn tune(&mut self, k: f64, mut report: impl FnMut(usize, f64)) {
+        const BETA1: f64 = 0.9;
+        const BETA2: f64 = 0.999;
+        const EPSILON: f64 = 1e-8;
+        let mut m = vec![0.0; NUM_EVAL_PARAMS];
+        let mut v = vec![0.0; NUM_EVAL_PARAMS];
+
+        for epoch in 1..=self.epochs {
+            let gradient = self.gradient(k);
+            for i in 0..NUM_EVAL_PARAMS {
+                m[i] = BETA1 * m[i] + (1.0 - BETA1) * gradient[i];
+                v[i] = BETA2 * v[i] + (1.0 - BETA2) * gradient[i] * gradient[i];
+                let m_hat = m[i] / (1.0 - BETA1.powi(epoch as i32));
+                let v_hat = v[i] / (1.0 - BETA2.powi(epoch as i32));
+                self.params[i] -= self.learning_rate * m_hat / (v_hat.sqrt() + EPSILON);
+            }
+            report(epoch, self.error(k));
+        }
+    }
+
+    /// The tuned parameters, rounded.
+    pub fn params(&self) -> Vec<i16> {
+        self.params.iter().map(|p| p.round() as i16).collect()
+    }
+
+    fn eval(&self, entry: &Entry) -> f64 {
+        let (mg, eg) = self.features[entry.start..entry.start + entry.len].iter()
+            .fold((0.0, 0.0), |(mg, eg), &(i, count)| {
+                (mg + count as f64 * self.params[i as usize], eg + count as f64 * self.params[EG_PARAMS + i as usize])
+            });
+        (mg * entry.phase as f64 + eg * (24 - entry.phase) as f64) / 24.0
+    }
+
+    /// Gradient of the error over all positions, split between the available cores.
+    fn gradient(&self, k: f64) -> Vec<f64> {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk = self.len().div_ceil(threads).max(1);
+        let partials: Vec<Vec<f64>> = thread::scope(|scope| {
+            let handles: Vec<_> = self.entries.chunks(chunk).map(|entries| scope.spawn(move || {
+                let mut gradient = vec![0.0; NUM_EVAL_PARAMS];
+                for entry in entries {
+                    let predicted = sigmoid(k, self.eval(entry));
+                    // d/d(eval) of (result - sigmoid)^2
+                    let slope = -2.0 * (entry.result - predicted) * predicted * (1.0 - predicted)
+                        * k * std::f64::consts::LN_10 / 400.0;
+                    let mg = slope * entry.phase as f64 / 24.0;
+                    let eg = slope * (24 - entry.phase) as f64 / 24.0;
+                    for &(i, count) in &self.features[entry.start..entry.start + entry.len] {
+                        gradient[i as usize] += mg * count as f64;
+                        gradient[EG_PARAMS + i as usize] += eg * count as f64;
+                    }
+                }
+                gradient
+            })).collect();
+            handles.into_iter().map(|h| h.join().expect("tuner thread panicked")).collect()
+        });
+
+        let mut gradient = vec![0.0; NUM_EVAL_PARAMS];
+        for partial in partials {
+            gradient.iter_mut().zip(partial).for_each(|(g, p)| *g += p);
+        }
+        gradient.iter_mut().for_each(|g| *g /= self.len().max(1) as f64);
+        gradient
+    }
+}
+
+#[inline(always)]
+fn sigmoid(k: f64, eval: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-k * eval / 400.0))
+}
+
+/// Counts of the eval terms, as the eval modules report them.
+struct Counts([f32; EG_PARAMS]);
+
+impl Terms for Counts {
+    fn add(&mut self, index: usize, count: i32) {
+        self.0[index] += count as f32;
+    }
+
+    fn add_percent(&mut self, index: usize, percent: i32) {
+        self.0[index] += percent as f32 / 100.0;
+    }
+}
+
+/// How often each middlegame parameter counts in the eval of `pos`, White minus Black. The
+/// endgame parameters count just as often; only the phase weights differ.
+fn coefficients(pos: &Position) -> [f32; EG_PARAMS] {
+    let mut counts = [0.0; EG_PARAMS];
+    for piece in 0..6 {
+        for color in [Color::White, Color::Black] {
+            let sign = if color.is_white() { 1 } else { -1 };
+            let (squares, count) = pos.piece_list(Piece::from(piece), color);
+            for &sq in &squares[..count] {
+                let sq = if color.is_white() { mirror(sq as usize) } else { sq as usize };
+                counts[MATERIAL_INDEX + piece] += sign as f32;
+                counts[PST_INDEX + piece * 64 + sq] += sign as f32;
+            }
+        }
+    }
+    let simplified = (MAX_NONPAWN_START - pos.count_nonpawn_pieces_total()).max(0);
+    counts[SIMPLIFY_INDEX] = (pos.raw_material_diff().signum() * simplified) as f32;
+
+    let mut counts = Counts(counts);
+    pawns::terms(pos, &mut counts);
+    king_safety::terms(pos, &mut counts);
+    mobility::terms(pos, &mut counts);
+    counts.0
+}
+
+/// A FEN and White's result from one line of a training file.
+fn parse_line(line: &str) -> Option<(String, f64)> {
+    if line.contains('|') {
+        let record = Record::from_text(line)?;
+        return Some((record.fen, record.result as f64));
+    }
+    const RESULTS: [(&str, f64); 8] = [
+        ("[1.0]", 1.0), ("[0.5]", 0.5), ("[0.0]", 0.0), ("[1]", 1.0), ("[0]", 0.0),
+        ("1/2-1/2", 0.5), ("1-0", 1.0), ("0-1", 0.0),
+    ];
+    let (at, result) = RESULTS.iter()
+        .filter_map(|&(text, result)| line.find(text).map(|at| (at, result)))
+        .min_by_key(|&(at, _)| at)?;
+
+    // the FEN is four fields, or six when the move counters are there too
+    let fields: Vec<&str> = line[..at].split_whitespace().collect();
+    let counters = fields.len() >= 6 && fields[4..6].iter().all(|f| f.parse::<u16>().is_ok());
+    let len = if counters { 6 } else { 4 };
+    (fields.len() >= 4).then(|| (fields[..len].join(" "), result))
+}
+
+
+/// Rust source of eval_params.rs holding `params`.
+pub fn emit_rust(params: &[i16]) -> String {
+    assert_eq!(params.len(), NUM_EVAL_PARAMS);
+    let row = |values: &[i16]| {
+        let row: String = values.iter().map(|v| format!("{v:>5},")).collect();
+        format!("    {row}\n")
+    };
+
+    let mut out = String::new();
+    out += "// Written by the tuner (`cargo run --release --bin tune`): re-run it rather than editing by hand.\n";
+    out += "use crate::eval::NUM_EVAL_PARAMS;\n\n";
+    out += "/// Every parameter of the tapered eval in one vector, middlegame half first and the endgame\n";
+    out += "/// half laid out the same way; `eval.rs` names the groups.\n";
+    out += "pub const EVAL_PARAMS: [i16; NUM_EVAL_PARAMS] = [\n";
+    for (phase, half) in ["middlegame", "endgame"].iter().zip(params.chunks(EG_PARAMS)) {
+        out += &format!("    // {phase} material: pawn, knight, bishop, rook, queen, king\n");
+        out += &row(&half[MATERIAL_INDEX..MATERIAL_INDEX + 6]);
+        for (piece, name) in PIECE_NAMES.iter().enumerate() {
+            out += &format!("    // {phase} {name} table, a8 first\n");
+            for rank in half[PST_INDEX + piece * 64..PST_INDEX + (piece + 1) * 64].chunks(8) {
+                out += &row(rank);
+            }
+        }
+        for (i, &(name, start)) in GROUPS.iter().enumerate() {
+            let end = GROUPS.get(i + 1).map_or(EG_PARAMS, |&(_, next)| next);
+            out += &format!("    // {phase} {name}\n");
+            for values in half[start..end].chunks(8) {
+                out += &row(values);
+            }
+        }
+    }
+    out += "];\n";
+    out
+}
@@ -0,0 +1,434 @@
+use std::io::{self, BufRead};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use crate::color::Color;
+use crate::engines::bitbase;
+use crate::engines::book::Book;
+use crate::engines::nnue::Network;
+use crate::engines::search_params::{SearchParams, PARAM_SPECS};
+use crate::engines::engine_manager::{Engine, SearchInfo, SearchLimits, DEFAULT_HASH_MB, NUMBER_OF_EVAL_ALGORITHMS};
+use crate::engines::score::Score;
+use crate::engines::time_manager::{Clock, DEFAULT_MOVE_OVERHEAD};
+use crate::mov::Move;
+use crate::position::Position;
+
+const ENGINE_NAME:    &str = "Magnus Carlblunder";
+const ENGINE_AUTHOR:  &str = "Peter Johnstone";
+const DEFAULT_SEARCH: u8   = 29;
+const MAX_THREADS:    u64  = 64;
+const MAX_HASH_MB:    u64  = 4096;
+
+const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+
+/// Arguments of a `go` command. Times are in milliseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GoParams {
+    pub wtime:     Option<u64>,
+    pub btime:     Option<u64>,
+    pub winc:      Option<u64>,
+    pub binc:      Option<u64>,
+    pub movestogo: Option<u64>,
+    pub depth:     Option<u8>,
+    pub nodes:     Option<u64>,
+    pub movetime:  Option<u64>,
+    pub mate:      Option<u8>,
+    pub infinite:  bool,
+}
+
+impl GoParams {
+    pub fn parse(args: &str) -> GoParams {
+        let mut params = GoParams::default();
+        let mut tokens = args.split_whitespace();
+        while let Some(token) = tokens.next() {
+            match token {
+                "wtime"     => params.wtime     = next_number(&mut tokens),
+                "btime"     => params.btime     = next_number(&mut tokens),
+                "winc"      => params.winc      = next_number(&mut tokens),
+                "binc"      => params.binc      = next_number(&mut tokens),
+                "movestogo" => params.movestogo = next_number(&mut tokens),
+                "depth"     => params.depth     = next_number(&mut tokens),
+                "nodes"     => params.nodes     = next_number(&mut tokens),
+                "movetime"  => params.movetime  = next_number(&mut tokens),
+                "mate"      => params.mate      = next_number(&mut tokens),
+                "infinite"  => params.infinite  = true,
+                _ => {}
+            }
+        }
+        params
+    }
+
+    /// The clock of `side`, unless the search is not meant to be budgeted from it
+    /// (`infinite`, `movetime`, or no time given at all).
+    pub fn clock(&self, side: Color) -> Option<Clock> {
+        if self.infinite || self.movetime.is_some() {
+            return None;
+        }
+        let (time, inc) = if side.is_white() { (self.wtime, self.winc) } else { (self.btime, self.binc) };
+        Some(Clock { remaining: time?, increment: inc.unwrap_or(0), moves_to_go: self.movestogo })
+    }
+
+    /// Everything but the clock. Searches with neither a clock nor `movetime` (`go depth 8`,
+    /// `go nodes 10000`, `go mate 3`) get no time limit at all.
+    pub fn limits(&self, side: Color) -> SearchLimits {
+        SearchLimits {
+            depth:    self.depth,
+            nodes:    self.nodes,
+            movetime: self.movetime,
+            infinite: self.infinite || (self.movetime.is_none() && self.clock(side).is_none()),
+            mate:     self.mate,
+        }
+    }
+}
+
+fn next_number<T: std::str::FromStr>(tokens: &mut std::str::SplitWhitespace) -> Option<T> {
+    tokens.next().and_then(|t| t.parse().ok())
+}
+
+
+/// Parses the arguments of a `position` command: `startpos | fen <fen>` followed by `moves ...`.
+pub fn parse_position(args: &str) -> Result<Position, String> {
+    let (setup, moves) = match args.find("moves") {
+        Some(i) => (args[..i].trim(), args[i + "moves".len()..].trim()),
+        None    => (args.trim(), ""),
+    };
+
+    let mut position = if setup == "startpos" {
+        Position::load_position_from_fen(START_FEN)
+    } else if let Some(fen) = setup.strip_prefix("fen") {
+        Position::from_fen(fen.trim()).map_err(|e| format!("invalid fen: {e}"))?
+    } else {
+        return Err(format!("unknown position setup '{setup}'"));
+    };
+
+    for text in moves.split_whitespace() {
+        let mov = position.parse_uci_move(text).map_err(|e| e.to_string())?;
+        position.do_move(mov);
+        if position.undo_stack.is_near_full() {
+            position.undo_stack.make_space();
+        }
+    }
+    Ok(position)
+}
+
+/// `score cp <x>` or `score mate <n>` for a side-to-move score.
+pub fn format_score(score: Score) -> String {
+    match score {
+        Score::Cp(cp)  => format!("score cp {cp}"),
+        Score::Mate(n) => format!("score mate {n}"),
+    }
+}
+
+/// `bestmove <move>`, or UCI's null move `0000` when there is no legal move.
+pub fn format_bestmove(mv: Move) -> String {
+    if mv.is_null() { "bestmove 0000".to_string() } else { format!("bestmove {mv}") }
+}
+
+pub fn format_info(info: &SearchInfo) -> String {
+    let pv: Vec<String> = info.pv.iter().map(Move::to_string).collect();
+    format!(
+        "info depth {} {} nodes {} nps {} hashfull {} time {} pv {}",
+        info.depth,
+        format_score(info.score),
+        info.nodes,
+        info.nps(),
+        info.hashfull,
+        info.elapsed.as_millis(),
+        pv.join(" "),
+    )
+}
+
+
+pub struct Uci {
+    position:    Option<Position>,  // None after an invalid `position` command
+    engine:      Option<Engine>,
+    search:      Option<JoinHandle<Engine>>,
+    infinite:    bool,
+    stop:        Arc<AtomicBool>,
+    search_algo: u8,
+    eval_algo:   u8,
+    move_overhead: u64,
+    threads:     usize,
+    hash_mb:     usize,
+    book:        Option<Book>,
+    network:     Option<Arc<Network>>,
+    search_params: SearchParams,
+    syzygy_path: String,
+}
+
+impl Default for Uci {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Uci {
+    pub fn new() -> Uci {
+        let mut uci = Uci {
+            position: Some(Position::load_position_from_fen(START_FEN)),
+            engine: None,
+            search: None,
+            infinite: false,
+            stop: Arc::new(AtomicBool::new(false)),
+            search_algo: DEFAULT_SEARCH,
+            eval_algo: NUMBER_OF_EVAL_ALGORITHMS,
+            move_overhead: DEFAULT_MOVE_OVERHEAD,
+            threads: 1,
+            hash_mb: DEFAULT_HASH_MB,
+            book: None,
+            network: None,
+            search_params: SearchParams::default(),
+            syzygy_path: String::new(),
+        };
+        uci.reset_engine();
+        uci
+    }
+
+    /// Replaces the (idle) engine with a fresh one built from the current options.
+    fn reset_engine(&mut self) {
+        let mut engine = Engine::new(self.search_algo, self.eval_algo, 0);
+        engine.set_move_overhead(self.move_overhead);
+        engine.set_threads(self.threads);
+        if self.hash_mb != DEFAULT_HASH_MB {
+            engine.set_hash_mb(self.hash_mb);
+        }
+        engine.set_book(self.book.clone());
+        engine.set_network(self.network.clone());
+        engine.set_search_params(self.search_params);
+        if let Err(e) = engine.set_syzygy_path(&self.syzygy_path) {
+            println!("info string cannot load tablebases from '{}': {e}", self.syzygy_path);
+        }
+        engine.set_info_callback(|info| println!("{}", format_info(info)));
+        self.stop   = engine.stop_flag();
+        self.engine = Some(engine);
+    }
+
+    /// Reads commands from stdin until `quit` or end of input.
+    pub fn run(&mut self) {
+        let stdin = io::stdin();
+        for line in stdin.lock().lines() {
+            let Ok(line) = line else { break };
+            if !self.handle(&line) {
+                self.stop_search();
+                return;
+            }
+        }
+        self.finish_search();
+    }
+
+    /// Handles one command line. Returns `false` once the engine should quit.
+    pub fn handle(&mut self, line: &str) -> bool {
+        let line = line.trim();
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+        match command {
+            "uci" => {
+                println!("id name {ENGINE_NAME}");
+                println!("id author {ENGINE_AUTHOR}");
+                println!("option name Search type spin default {DEFAULT_SEARCH} min 1 max 30");
+                println!("option name Eval type spin default {NUMBER_OF_EVAL_ALGORITHMS} min 1 max {NUMBER_OF_EVAL_ALGORITHMS}");
+                println!("option name Move Overhead type spin default {DEFAULT_MOVE_OVERHEAD} min 0 max 5000");
+                println!("option name Threads type spin default 1 min 1 max {MAX_THREADS}");
+                println!("option name Hash type spin default {DEFAULT_HASH_MB} min 1 max {MAX_HASH_MB}");
+                println!("option name Clear Hash type button");
+                println!("option name Book File type string default <empty>");
+                println!("option name EvalFile type string default <empty>");
+                if cfg!(feature = "syzygy") {
+                    println!("option name SyzygyPath type string default <empty>");
+                }
+                let defaults = SearchParams::default().values();
+                for (spec, default) in PARAM_SPECS.iter().zip(defaults) {
+                    println!("option name {} type spin default {default} min {} max {}", spec.name, spec.min, spec.max);
+                }
+                println!("uciok");
+            }
+            "isready"    => {
+                // the endgame bitbases take a moment to build; better now than mid-search
+                bitbase::init();
+                println!("readyok");
+            }
+            "setoption"  => self.set_option(args),
+            "ucinewgame" => {
+                self.stop_search();
+                self.engine.as_mut().expect("engine is idle").clear_hash();
+                self.position = Some(Position::load_position_from_fen(START_FEN));
+            }
+            "position" => {
+                self.finish_search();
+                self.position = match parse_position(args) {
+                    Ok(position) => Some(position),
+                    Err(e) => {
+                        // searching the previous position instead would play an illegal move
+                        println!("info string {e}");
+                        None
+                    }
+                };
+            }
+            "go"   => self.go(GoParams::parse(args)),
+            "stop" => self.stop_search(),
+            "quit" => return false,
+            _ => {}
+        }
+        true
+    }
+
+    fn set_option(&mut self, args: &str) {
+        // setoption name <id> value <x>
+        let Some(rest) = args.trim().strip_prefix("name") else { return };
+        let (name, value) = rest.split_once("value").unwrap_or((rest, ""));
+        if name.trim().eq_ignore_ascii_case("book file") {
+            self.set_book_file(value.trim());
+            return;
+        }
+        if name.trim().eq_ignore_ascii_case("evalfile") {
+            self.set_eval_file(value.trim());
+            return;
+        }
+        if name.trim().eq_ignore_ascii_case("syzygypath") {
+            self.set_syzygy_path(value.trim());
+            return;
+        }
+        // the search parameters, for tuning with external tools
+        if self.search_params.get(name.trim()).is_some() {
+            if let Ok(v) = value.trim().parse() {
+                self.search_params.set(name.trim(), v);
+                self.finish_search();
+                self.engine.as_mut().expect("engine is idle").set_search_params(self.search_params);
+            }
+            return;
+        }
+        let value: Option<u64> = value.trim().parse().ok();
+        match (name.trim().to_ascii_lowercase().as_str(), value) {
+            ("search", Some(v)) if (1..=30).contains(&v) => self.search_algo = v as u8,
+            ("eval", Some(v)) if (1..=NUMBER_OF_EVAL_ALGORITHMS as u64).contains(&v) => self.eval_algo = v as u8,
+            ("move overhead", Some(v)) if v <= 5000 => {
+                self.move_overhead = v;
+                self.finish_search();
+                self.engine.as_mut().expect("engine is idle").set_move_overhead(v);
+                return;
+            }
+            ("hash", Some(v)) if (1..=MAX_HASH_MB).contains(&v) => {
+                self.hash_mb = v as usize;
+                self.finish_search();
+                self.engine.as_mut().expect("engine is idle").set_hash_mb(self.hash_mb);
+                return;
+            }
+            ("clear hash", _) => {
+                self.finish_search();
+                self.engine.as_mut().expect("engine is idle").clear_hash();
+                return;
+            }
+            ("threads", Some(v)) if (1..=MAX_THREADS).contains(&v) => {
+                self.threads = v as usize;
+                self.finish_search();
+                self.engine.as_mut().expect("engine is idle").set_threads(self.threads);
+                return;
+            }
+            _ => {
+                println!("info string unsupported option '{}'", args.trim());
+                return;
+            }
+        }
+        self.finish_search();
+        self.reset_engine();
+    }
+
+    /// Loads a Polyglot book; an empty path or `<empty>` turns the book off.
+    fn set_book_file(&mut self, path: &str) {
+        self.book = match path {
+            "" | "<empty>" => None,
+            path => match Book::open(path) {
+                Ok(book) => Some(book),
+                Err(e) => {
+                    println!("info string cannot open book '{path}': {e}");
+                    None
+                }
+            },
+        };
+        self.finish_search();
+        self.engine.as_mut().expect("engine is idle").set_book(self.book.clone());
+    }
+
+    /// Loads NNUE weights for the NNUE eval; an empty path or `<empty>` unloads them.
+    fn set_eval_file(&mut self, path: &str) {
+        self.network = match path {
+            "" | "<empty>" => None,
+            path => match Network::open(path) {
+                Ok(network) => Some(Arc::new(network)),
+                Err(e) => {
+                    println!("info string cannot load network '{path}': {e}");
+                    None
+                }
+            },
+        };
+        self.finish_search();
+        self.engine.as_mut().expect("engine is idle").set_network(self.network.clone());
+    }
+
+    fn set_syzygy_path(&mut self, path: &str) {
+        self.syzygy_path = if path == "<empty>" { String::new() } else { path.to_string() };
+        self.finish_search();
+        match self.engine.as_mut().expect("engine is idle").set_syzygy_path(&self.syzygy_path) {
+            Ok(0) if !self.syzygy_path.is_empty() => println!("info string no tablebases found in '{}'", self.syzygy_path),
+            Ok(0) => {}
+            Ok(pieces) => println!("info string tablebases loaded, up to {pieces} pieces"),
+            Err(e) => {
+                println!("info string cannot load tablebases from '{}': {e}", self.syzygy_path);
+                self.syzygy_path.clear();
+                let _ = self.engine.as_mut().expect("engine is idle").set_syzygy_path("");
+            }
+        }
+    }
+
+    fn go(&mut self, params: GoParams) {
+        self.finish_search();
+        let Some(position) = &self.position else {
+            println!("info string no valid position to search");
+            println!("{}", format_bestmove(Move::null()));
+            return;
+        };
+        // book moves are answered straight away; analysis always searches
+        if !params.infinite {
+            let book_move = self.engine.as_ref().expect("engine is idle").book_move(position);
+            if let Some(mv) = book_move {
+                println!("bestmove {mv}");
+                return;
+            }
+        }
+        let mut engine = self.engine.take().expect("engine is idle");
+        let side = position.side_to_move();
+        engine.set_clock(params.clock(side));
+        engine.set_limits(params.limits(side));
+        self.stop.store(false, Ordering::Relaxed);
+        self.infinite = params.infinite;
+
+        let mut position = position.clone();
+        let stop = Arc::clone(&self.stop);
+        self.search = Some(thread::spawn(move || {
+            let (best, _, _) = engine.pick_and_stats(&mut position);
+            // `go infinite` must not answer before the GUI says `stop`
+            while params.infinite && !stop.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(1));
+            }
+            println!("{}", format_bestmove(best));
+            engine
+        }));
+    }
+
+    /// Stops a running search (if any) and waits for its `bestmove`.
+    fn stop_search(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.finish_search();
+    }
+
+    /// Waits for a running search to hit its own limits. Infinite searches are stopped instead.
+    fn finish_search(&mut self) {
+        if self.infinite {
+            self.stop.store(true, Ordering::Relaxed);
+        }
+        if let Some(handle) = self.search.take() {
+            self.engine = Some(handle.join().expect("search thread panicked"));
+        }
+    }
+}
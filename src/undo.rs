@@ -15,6 +15,7 @@ pub struct Undo {
     pub(crate) en_passant:          u8,
     pub(crate) half_move:           u8,
     pub(crate) zobrist:             u64,
+    pub(crate) pawn_key:            u64,
     pub(crate) state_info:          StateInfo,
     pub(crate) mov:                 Move,
     pub(crate) delta_raw_piece_diff:i32,
@@ -40,6 +41,7 @@ impl UndoStack {
             en_passant: NO_SQ,
             half_move: 0,
             zobrist: 0,
+            pawn_key: 0,
             state_info: StateInfo::default(),
             mov: Move::null(),
             delta_raw_piece_diff: 0,
@@ -0,0 +1,33 @@
+use chess::engines::engine_manager::Engine;
+use chess::simulator::engine_battle_simulator::{simulate_many_battles, Openings, Outcome, PairResult};
+
+mod common;
+
+fn engine(search: u8) -> Engine {
+    common::engine_with(search, 5, common::node_limit(200))
+}
+
+#[test]
+fn pair_records() {
+    let pair = |as_white, as_black| PairResult { opening: 0, as_white, as_black }.record();
+    assert_eq!(pair(Outcome::Win, Outcome::Win), (2, 0, 0));
+    assert_eq!(pair(Outcome::Loss, Outcome::Draw), (0, 1, 1));
+    assert_eq!(pair(Outcome::Draw, Outcome::Win), (1, 0, 1));
+}
+
+#[test]
+fn results_belong_to_the_engine_not_the_color() {
+    // search 1 plays random moves
+    let (strong, weak) = (engine(29), engine(1));
+
+    let (stats, pairs) = simulate_many_battles(&mut strong.clone(), &mut weak.clone(), 4, Openings::Sequential(10));
+    assert_eq!(stats.pairs(), 4);
+    assert!(stats.wins > stats.losses);
+    // each opening once, in sequence
+    let mut openings: Vec<usize> = pairs.iter().map(|pair| pair.opening).collect();
+    openings.sort();
+    assert_eq!(openings, vec![10, 11, 12, 13]);
+
+    let (stats, _) = simulate_many_battles(&mut weak.clone(), &mut strong.clone(), 4, Openings::Sequential(10));
+    assert!(stats.losses > stats.wins);
+}
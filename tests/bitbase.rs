@@ -0,0 +1,66 @@
+use chess::color::Color;
+use chess::engines::bitbase::{self, Ending, KNOWN_WIN};
+use chess::engines::engine_manager::{Engine, NUMBER_OF_EVAL_ALGORITHMS};
+use chess::position::Position;
+
+fn probe(fen: &str) -> Option<(Ending, Color, bool)> {
+    bitbase::probe(&Position::from_fen(fen).unwrap())
+}
+
+fn wins(fen: &str) -> bool {
+    probe(fen).unwrap().2
+}
+
+#[test]
+fn queen_and_rook_endings() {
+    assert!(wins("8/8/8/3k4/8/8/8/Q3K3 w - - 0 1"));
+    assert!(wins("8/8/8/4k3/8/8/8/R3K3 b - - 0 1"));
+    // the queen hangs
+    assert!(!wins("8/8/8/8/8/8/1kQ5/4K3 b - - 0 1"));
+    // stalemate
+    assert!(!wins("k7/2Q5/1K6/8/8/8/8/8 b - - 0 1"));
+    // mated already
+    assert!(wins("k7/1Q6/1K6/8/8/8/8/8 b - - 0 1"));
+}
+
+#[test]
+fn pawn_endings() {
+    // king in front of the pawn on the sixth rank wins whoever moves
+    assert!(wins("4k3/8/4K3/4P3/8/8/8/8 w - - 0 1"));
+    assert!(wins("4k3/8/4K3/4P3/8/8/8/8 b - - 0 1"));
+    // the pawn ahead of its king on the sixth rank draws
+    assert!(!wins("4k3/8/4P3/4K3/8/8/8/8 w - - 0 1"));
+    assert!(!wins("4k3/8/4P3/4K3/8/8/8/8 b - - 0 1"));
+    // rook pawn with the defender in the corner
+    assert!(!wins("k7/8/8/8/8/8/P7/1K6 w - - 0 1"));
+    // the pawn runs when the king is outside its square
+    assert!(wins("8/7k/8/8/8/8/1P6/K7 w - - 0 1"));
+}
+
+#[test]
+fn black_strong_side_is_mirrored() {
+    let (ending, strong, win) = probe("8/8/8/8/4k3/4p3/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(ending, Ending::Kpk);
+    assert!(strong.is_black());
+    assert_eq!(win, wins("4k3/8/4P3/4K3/8/8/8/8 b - - 0 1"));
+
+    assert!(!wins("8/8/8/8/4k3/4p3/8/4K3 b - - 0 1"));
+    assert!(wins("4K3/8/4k3/8/8/8/8/3q4 w - - 0 1"));
+}
+
+#[test]
+fn evaluation_uses_bitbases() {
+    // every eval gets the bitbase score, probed once by the engine
+    let evaluate = |eval: u8, fen: &str| Engine::new(29, eval, 0).evaluate(&Position::from_fen(fen).unwrap());
+    let won = "8/8/8/4k3/8/8/8/R3K3 w - - 0 1";
+    for eval in 1..=NUMBER_OF_EVAL_ALGORITHMS {
+        assert!(evaluate(eval, won) >= KNOWN_WIN);
+    }
+    assert!(evaluate(2, "4K3/8/4k3/8/8/8/8/3q4 w - - 0 1") <= -KNOWN_WIN);
+    assert_eq!(evaluate(2, "4k3/8/4P3/4K3/8/8/8/8 w - - 0 1"), 0);
+
+    // the weak king on the edge is worth more than in the centre, so the search makes progress
+    assert!(evaluate(2, "4k3/8/8/8/8/8/8/R3K3 w - - 0 1") > evaluate(2, won));
+
+    assert!(bitbase::evaluate(&Position::start()).is_none());
+}
@@ -0,0 +1,196 @@
+use chess::engines::book::{Book, BookBuilder, BookSelection, MoveStats};
+use chess::engines::engine_manager::Engine;
+use chess::position::Position;
+
+fn play(moves: &[&str]) -> Position {
+    let mut pos = Position::start();
+    for mv in moves {
+        let mv = pos.parse_uci_move(mv).unwrap();
+        pos.do_move(mv);
+    }
+    pos
+}
+
+/// Builds a polyglot move: to square in bits 0-5, from square in bits 6-11.
+fn poly_move(from: u16, to: u16) -> u16 {
+    from << 6 | to
+}
+
+fn book_bytes(entries: &[(u64, u16, u16)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for &(key, mv, weight) in entries {
+        bytes.extend(key.to_be_bytes());
+        bytes.extend(mv.to_be_bytes());
+        bytes.extend(weight.to_be_bytes());
+        bytes.extend(0u32.to_be_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn polyglot_keys_match_reference() {
+    // reference values from the Polyglot book format description
+    let cases: [(&[&str], u64); 9] = [
+        (&[], 0x463b96181691fc9c),
+        (&["e2e4"], 0x823c9b50fd114196),
+        (&["e2e4", "d7d5"], 0x0756b94461c50fb0),
+        (&["e2e4", "d7d5", "e4e5"], 0x662fafb965db29d4),
+        (&["e2e4", "d7d5", "e4e5", "f7f5"], 0x22a48b5a8e47ff78),
+        (&["e2e4", "d7d5", "e4e5", "f7f5", "e1e2"], 0x652a607ca3f242c1),
+        (&["e2e4", "d7d5", "e4e5", "f7f5", "e1e2", "e8f7"], 0x00fdd303c946bdd9),
+        (&["a2a4", "b7b5", "h2h4", "b5b4", "c2c4"], 0x3c8123ea7b067637),
+        (&["a2a4", "b7b5", "h2h4", "b5b4", "c2c4", "b4c3", "a1a3"], 0x5c3f9b829b279560),
+    ];
+    for (moves, key) in cases {
+        assert_eq!(play(moves).polyglot_key(), key, "after {moves:?}");
+    }
+}
+
+#[test]
+fn best_and_weighted_selection() {
+    let start = Position::start().polyglot_key();
+    let e2e4 = poly_move(12, 28);
+    let d2d4 = poly_move(11, 27);
+    let mut book = Book::from_bytes(&book_bytes(&[
+        (start, d2d4, 5),
+        (0x1234, e2e4, 100),  // some other position
+        (start, e2e4, 10),
+    ]));
+    assert_eq!(book.len(), 3);
+
+    let pos = Position::start();
+    let moves: Vec<String> = book.moves(&pos).iter().map(|(mv, _)| mv.to_string()).collect();
+    assert_eq!(moves, ["e2e4", "d2d4"]);
+
+    book.selection = BookSelection::Best;
+    let mut rng = rand::rng();
+    for _ in 0..20 {
+        assert_eq!(book.probe(&pos, &mut rng).unwrap().to_string(), "e2e4");
+    }
+
+    book.selection = BookSelection::Weighted;
+    let picks: Vec<String> = (0..200).map(|_| book.probe(&pos, &mut rng).unwrap().to_string()).collect();
+    assert!(picks.iter().any(|mv| mv == "e2e4"));
+    assert!(picks.iter().any(|mv| mv == "d2d4"));
+
+    // out of book
+    assert!(book.probe(&play(&["a2a3"]), &mut rng).is_none());
+}
+
+#[test]
+fn max_depth_limits_book_use() {
+    let pos = play(&["e2e4"]);
+    let e7e5 = poly_move(52, 36);
+    let mut book = Book::from_bytes(&book_bytes(&[(pos.polyglot_key(), e7e5, 1)]));
+    let mut rng = rand::rng();
+
+    book.max_depth = Some(2);
+    assert_eq!(book.probe(&pos, &mut rng).unwrap().to_string(), "e7e5");
+    book.max_depth = Some(1);
+    assert!(book.probe(&pos, &mut rng).is_none());
+
+    // a full-move number of 0 counts as the first move
+    let pos = Position::load_position_from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 0");
+    assert_eq!(book.probe(&pos, &mut rng), None);
+    book.max_depth = Some(2);
+    assert_eq!(book.probe(&pos, &mut rng).unwrap().to_string(), "e7e5");
+}
+
+#[test]
+fn castling_is_king_takes_rook() {
+    let pos = Position::from_fen("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1").unwrap();
+    let book = Book::from_bytes(&book_bytes(&[
+        (pos.polyglot_key(), poly_move(4, 7), 2),   // e1h1
+        (pos.polyglot_key(), poly_move(4, 0), 1),   // e1a1
+    ]));
+    let moves: Vec<String> = book.moves(&pos).iter().map(|(mv, _)| mv.to_string()).collect();
+    assert_eq!(moves, ["e1g1", "e1c1"]);
+}
+
+#[test]
+fn engine_plays_book_move() {
+    let pos = Position::start();
+    let mut book = Book::from_bytes(&book_bytes(&[(pos.polyglot_key(), poly_move(6, 21), 1)]));
+    book.selection = BookSelection::Best;
+
+    let mut engine = Engine::new(29, 2, 10_000);
+    engine.set_book(Some(book));
+    assert_eq!(engine.pick(&pos).to_string(), "g1f3");
+    assert!(engine.clone().book().is_some());
+}
+
+const GAMES: &str = r#"
+[Event "1"]
+1. e4 e5 2. Nf3 Nc6 1-0
+
+[Event "2"]
+1. e4 c5 0-1
+
+[Event "3"]
+1. e4 e5 2. Nf3 Nf6 1/2-1/2
+
+[Event "4"]
+1. d4 d5 1-0
+
+[Event "5"]
+1. e4 e5 *
+"#;
+
+#[test]
+fn builder_counts_results_per_move() {
+    let mut builder = BookBuilder::new();
+    assert_eq!(builder.add_pgn(GAMES).unwrap(), 4);  // the unfinished game is skipped
+
+    let start = Position::start();
+    let e4 = start.parse_uci_move("e2e4").unwrap();
+    assert_eq!(builder.stats(&start, e4), MoveStats { wins: 1, draws: 1, losses: 1 });
+
+    // black's results are counted from black's side
+    let after_e4 = play(&["e2e4"]);
+    let c5 = after_e4.parse_uci_move("c7c5").unwrap();
+    assert_eq!(builder.stats(&after_e4, c5), MoveStats { wins: 1, draws: 0, losses: 0 });
+
+    let book = builder.build();
+    let moves = book.moves(&start);
+    assert_eq!(moves.iter().map(|(mv, w)| (mv.to_string(), *w)).collect::<Vec<_>>(), [("e2e4".to_string(), 3), ("d2d4".to_string(), 2)]);
+
+    // written out and read back as a Polyglot file
+    let reread = Book::from_bytes(&book.to_bytes());
+    assert_eq!(reread.entries(), book.entries());
+}
+
+#[test]
+fn builder_filters() {
+    let mut builder = BookBuilder::new();
+    builder.add_pgn(GAMES).unwrap();
+    let start = Position::start();
+
+    builder.min_games = 2;
+    let book = builder.build();
+    assert_eq!(book.moves(&start).len(), 1);
+    assert!(book.moves(&play(&["d2d4"])).is_empty());
+
+    builder.min_games = 1;
+    builder.min_score = 0.6;
+    let book = builder.build();
+    let moves: Vec<String> = book.moves(&start).iter().map(|(mv, _)| mv.to_string()).collect();
+    assert_eq!(moves, ["d2d4"]);
+
+    // the ply limit applies while counting
+    let mut builder = BookBuilder::new();
+    builder.max_ply = 1;
+    builder.add_pgn(GAMES).unwrap();
+    assert!(builder.build().moves(&play(&["e2e4"])).is_empty());
+}
+
+#[test]
+fn builder_writes_castling_as_king_takes_rook() {
+    let mut builder = BookBuilder::new();
+    builder.add_pgn("1. e4 e5 2. Nf3 Nc6 3. Bc4 Bc5 4. O-O Nf6 1-0").unwrap();
+    let book = builder.build();
+
+    let before = play(&["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "f8c5"]);
+    let entry = book.entries().iter().find(|e| e.key == before.polyglot_key()).unwrap();
+    assert_eq!(entry.mv, poly_move(4, 7));
+    assert_eq!(book.moves(&before)[0].0.to_string(), "e1g1");
+}
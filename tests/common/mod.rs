@@ -0,0 +1,24 @@
+// Helpers shared by the integration tests; each test crate uses only some of them.
+#![allow(dead_code)]
+
+use chess::engines::engine_manager::{Engine, SearchLimits};
+
+/// An engine that stops on `limits` alone, never on the clock.
+pub fn engine_with(search: u8, eval: u8, limits: SearchLimits) -> Engine {
+    let mut engine = Engine::new(search, eval, 60_000);
+    engine.set_limits(limits);
+    engine
+}
+
+/// The strongest search with the plain eval, stopping on `limits` alone.
+pub fn engine(limits: SearchLimits) -> Engine {
+    engine_with(29, 2, limits)
+}
+
+pub fn depth_limit(depth: u8) -> SearchLimits {
+    SearchLimits { depth: Some(depth), infinite: true, ..Default::default() }
+}
+
+pub fn node_limit(nodes: u64) -> SearchLimits {
+    SearchLimits { nodes: Some(nodes), infinite: true, ..Default::default() }
+}
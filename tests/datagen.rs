@@ -0,0 +1,44 @@
+use std::fs;
+use chess::position::Position;
+use chess::simulator::datagen::{pack, Datagen, Record, RECORD_SIZE};
+
+fn small_run(positions: u64) -> Datagen {
+    Datagen { positions, threads: 2, nodes: 300, ..Datagen::new() }
+}
+
+#[test]
+fn records_round_trip() {
+    let fen = "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N2N2/PP2BPPP/R2QKB1R b - - 3 8";
+    let record = Record::from_bytes(&pack(&Position::from_fen(fen).unwrap(), -35, 0.5));
+    assert_eq!(record, Record { fen: fen.to_string(), score: -35, result: 0.5 });
+    assert_eq!(Record::from_text(&record.to_text()), Some(record));
+}
+
+#[test]
+fn generates_quiet_positions_and_resumes() {
+    let dir = std::env::temp_dir().join("chess_datagen_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let (binary, text) = (dir.join("data.bin"), dir.join("data.txt"));
+
+    assert_eq!(small_run(40).run(&binary, Some(&text)).unwrap(), 40);
+    assert_eq!(fs::metadata(&binary).unwrap().len(), 40 * RECORD_SIZE as u64);
+
+    // an interrupted run: half a record at the end of the binary file
+    let mut bytes = fs::read(&binary).unwrap();
+    bytes.extend_from_slice(&[0; RECORD_SIZE / 2]);
+    fs::write(&binary, bytes).unwrap();
+    assert_eq!(small_run(60).run(&binary, Some(&text)).unwrap(), 20);
+
+    let bytes = fs::read(&binary).unwrap();
+    let lines = fs::read_to_string(&text).unwrap();
+    assert_eq!(bytes.len(), 60 * RECORD_SIZE);
+    assert_eq!(lines.lines().count(), 60);
+    for (chunk, line) in bytes.chunks_exact(RECORD_SIZE).zip(lines.lines()) {
+        let record = Record::from_bytes(chunk.try_into().unwrap());
+        assert_eq!(Record::from_text(line).as_ref(), Some(&record));
+        assert!(!Position::from_fen(&record.fen).unwrap().in_check(), "{line}");
+        assert!([0.0, 0.5, 1.0].contains(&record.result));
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -1,84 +1,84 @@
 use chess::engines::engine_manager::Engine;
 use chess::position::Position;
-use chess::simulator::engine_battle_simulator::{battle_against_other_eval_algos, battle_against_other_search_algos, print_bar_graph, simulate_many_battles};
+use chess::simulator::engine_battle_simulator::{battle_against_other_eval_algos, battle_against_other_search_algos, print_bar_graph, simulate_many_battles, Openings};
 
 use funtime;
 
 
 #[test]
 fn eval_1() {
-    battle_against_other_eval_algos(5, 1, 2, 1000);
+    battle_against_other_eval_algos(5, 1, 2, 1000, Openings::Random);
 }
 
 #[test]
 fn eval_2() {
-    battle_against_other_eval_algos(13, 2, 10, 500);
+    battle_against_other_eval_algos(13, 2, 10, 500, Openings::Random);
 }
 
 #[test]
 fn eval_3() {
-    battle_against_other_eval_algos(12, 3, 10, 500);
+    battle_against_other_eval_algos(12, 3, 10, 500, Openings::Random);
 }
 
 
 #[test]
 fn search_3() {
-    battle_against_other_search_algos(3, 3, 2, 1000);
+    battle_against_other_search_algos(3, 3, 2, 1000, Openings::Random);
 }
 
 #[test]
 fn search_4() {
-    battle_against_other_search_algos(4, 3, 2, 1000);
+    battle_against_other_search_algos(4, 3, 2, 1000, Openings::Random);
 }
 #[test]
 fn search_5() {
-    battle_against_other_search_algos(5, 3, 2, 1000);
+    battle_against_other_search_algos(5, 3, 2, 1000, Openings::Random);
 }
 
 #[test]
 fn search_6() {
-    battle_against_other_search_algos(6, 3, 2, 1000);
+    battle_against_other_search_algos(6, 3, 2, 1000, Openings::Random);
 }
 
 #[test]
 fn search_7() {
-    battle_against_other_search_algos(7, 1, 30, 60);
+    battle_against_other_search_algos(7, 1, 30, 60, Openings::Random);
 }
 
 #[test]
 fn search_8() {
-    battle_against_other_search_algos(8, 1, 30, 60);
+    battle_against_other_search_algos(8, 1, 30, 60, Openings::Random);
 }
 
 
 #[test]
 fn search_9() {
-    battle_against_other_search_algos(9, 1, 10, 1000);
+    battle_against_other_search_algos(9, 1, 10, 1000, Openings::Random);
 }
 
 #[test]
 fn search_10() {
-    battle_against_other_search_algos(10, 1, 15, 1000);
+    battle_against_other_search_algos(10, 1, 15, 1000, Openings::Random);
 }
 
 #[test]
 fn search_11() {
-    battle_against_other_search_algos(11, 1, 40, 15);
+    battle_against_other_search_algos(11, 1, 40, 15, Openings::Random);
 }
 
 #[test]
 fn search_12() {
-    battle_against_other_search_algos(12, 1, 10, 500);
+    battle_against_other_search_algos(12, 1, 10, 500, Openings::Random);
 }
 
 #[test]
 fn search_13() {
-    battle_against_other_search_algos(13, 2, 10, 500);
+    battle_against_other_search_algos(13, 2, 10, 500, Openings::Random);
 }
 
 #[test]
 fn search_14() {
-    battle_against_other_search_algos(14, 2, 10, 500);
+    battle_against_other_search_algos(14, 2, 10, 500, Openings::Random);
 }
 
 
@@ -94,8 +94,8 @@ fn simplified_2() {
 --------------------------------------------------------------");
     let mut challenger = Engine::new(challenger_s, challenger_e, time_ms);
     let mut champion   = Engine::new(champion_s, champion_e, time_ms);
-    let (wins, losses, draws) = simulate_many_battles(&mut challenger, &mut champion, num_battles);
-    print_bar_graph(wins, losses, draws, challenger_s, challenger_e);
+    let (stats, _) = simulate_many_battles(&mut challenger, &mut champion, num_battles, Openings::Random);
+    print_bar_graph(&stats, challenger_s, challenger_e);
 }
 
 
@@ -106,8 +106,8 @@ fn engine_2() {
     let (champion_s, champion_e) = (4, 3);
     let mut challenger = Engine::new(challenger_s, challenger_e, 5);
     let mut champion = Engine::new(champion_s, champion_e, 5);
-    let (wins, losses, draws) = simulate_many_battles(&mut challenger, &mut champion, 100);
-    print_bar_graph(wins, losses, draws, challenger_s, challenger_s);
+    let (stats, _) = simulate_many_battles(&mut challenger, &mut champion, 100, Openings::Random);
+    print_bar_graph(&stats, challenger_s, challenger_s);
 }
 
 
@@ -0,0 +1,88 @@
+use chess::fen::{FenError, FenField};
+use chess::position::Position;
+
+mod perft_positions;
+use perft_positions::PERFT_POSITIONS;
+
+fn err(fen: &str) -> FenError {
+    Position::from_fen(fen).expect_err(fen)
+}
+
+#[test]
+fn accepts_valid_fens() {
+    for entry in PERFT_POSITIONS {
+        let fen = entry.fen;
+        let pos = Position::from_fen(fen).unwrap_or_else(|e| panic!("{fen}: {e}"));
+        assert_eq!(pos.zobrist(), Position::load_position_from_fen(fen).zobrist());
+    }
+    assert!(Position::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_ok());
+    assert!(Position::from_fen("  4k3/8/8/3pP3/8/8/8/4K3   w - d6 0 12 ").is_ok());
+    // full-move 0 is the first move, and long clocks are still a valid FEN
+    assert_eq!(Position::from_fen("8/8/8/8/3k4/8/6q1/4K3 b - - 0 0").unwrap().full_move(), 1);
+    assert!(Position::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 300 200").unwrap().half_move_over_ninety_nine());
+}
+
+#[test]
+fn board_errors() {
+    assert_eq!(err("8/8/8/8/8/8/8 w - -"), FenError::RankCount { offset: 0, ranks: 7 });
+    assert_eq!(err("4k3/8/8/45/8/8/8/4K3 w - -"), FenError::RankLength { offset: 8, rank: 5 });
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K4 w - -"), FenError::RankLength { offset: 16, rank: 1 });
+    assert_eq!(err("4k3/8/8/3x4/8/8/8/4K3 w - -"), FenError::InvalidPiece { offset: 9, found: 'x' });
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K2P w - -"), FenError::PawnOnBackRank { offset: 19 });
+    assert!(matches!(err("8/8/8/8/8/8/8/4K3 w - -"), FenError::KingCount { count: 0, .. }));
+    assert!(matches!(err("4kk2/8/8/8/8/8/8/4K3 w - -"), FenError::KingCount { count: 2, .. }));
+    assert!(matches!(err("QQQQQQQQ/QQQk4/8/8/8/8/8/4K3 w - -"), FenError::TooManyPieces { piece: 'Q', .. }));
+    // black is in check but it is white's turn
+    assert_eq!(err("4k3/8/8/8/8/8/8/4R1K1 w - -"), FenError::OpponentInCheck { offset: 0 });
+}
+
+#[test]
+fn field_errors() {
+    let e = err("4k3/8/8/8/8/8/8/4K3 x - -");
+    assert_eq!(e, FenError::SideToMove { offset: 20 });
+    assert_eq!(e.field(), FenField::SideToMove);
+
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w KX -"), FenError::Castling { offset: 23 });
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w KK -"), FenError::Castling { offset: 23 });
+    // rights without the king or rook at home
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w K -"), FenError::Castling { offset: 22 });
+    assert_eq!(err("r3k3/8/8/8/8/8/8/R3K2R w KQkq -"), FenError::Castling { offset: 27 });
+    assert_eq!(err("4k3/8/8/8/8/8/8/R4K1R w Q -"), FenError::Castling { offset: 24 });
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w - e3"), FenError::EnPassant { offset: 24 });
+    // no pawn that could just have double-pushed past the square
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w - d6"), FenError::EnPassant { offset: 24 });
+    assert_eq!(err("4k3/3p4/8/3pP3/8/8/8/4K3 w - d6"), FenError::EnPassant { offset: 29 });
+    assert_eq!(err("4k3/8/8/8/4P3/8/4P3/4K3 b - e3"), FenError::EnPassant { offset: 28 });
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K3 b - i3"), FenError::EnPassant { offset: 24 });
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w - - x 1"), FenError::HalfMove { offset: 26 });
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w - - 0 x"), FenError::FullMove { offset: 28 });
+    assert_eq!(err("4k3/8/8/8/8/8/8/4K3 w - - 0 1 x"), FenError::TrailingInput { offset: 30 });
+
+    let e = err("4k3/8/8/8/8/8/8/4K3 w");
+    assert_eq!(e, FenError::MissingField { field: FenField::Castling, offset: 21 });
+    assert_eq!(e.to_string(), "missing Castling field (at offset 21)");
+}
+
+#[test]
+fn round_trip() {
+    for entry in PERFT_POSITIONS {
+        // the perft FENs leave out both move counters
+        let fen = format!("{} 0 1", entry.fen);
+        let pos = Position::from_fen(entry.fen).unwrap();
+        assert_eq!(pos.to_fen(), fen);
+        assert_eq!(Position::from_fen(&fen).unwrap().to_fen(), fen);
+    }
+
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 37 61";
+    let mut pos = Position::from_fen(fen).unwrap();
+    assert_eq!(pos.half_move(), 37);
+    assert_eq!(pos.full_move(), 61);
+    assert_eq!(pos.to_fen(), fen);
+
+    for mv in chess::attacks::movegen::all_moves(&pos).iter() {
+        pos.do_move(mv);
+        assert_eq!(pos.full_move(), 62);
+        pos.undo_move();
+        assert_eq!(pos.to_fen(), fen);
+    }
+}
@@ -0,0 +1,30 @@
+use chess::attacks::movegen::all_moves;
+use chess::position::Position;
+
+mod common;
+use common::{depth_limit, engine};
+
+#[test]
+fn hash_size_and_clearing() {
+    let mut pos = Position::start();
+    let mut engine = engine(depth_limit(6));
+    assert_eq!(engine.hashfull(), 0);
+
+    engine.set_hash_mb(1);
+    assert_eq!(engine.hash_mb(), 1);
+    assert_eq!(engine.clone().hash_mb(), 1);
+    engine.pick_and_stats(&mut pos);
+    let full = engine.hashfull();
+    assert!(full > 0 && full <= 1000);
+
+    // a new search counts only its own entries, even before it overwrites the old ones
+    engine.set_limits(depth_limit(1));
+    engine.pick_and_stats(&mut pos);
+    assert!(engine.hashfull() < full);
+
+    engine.clear_hash();
+    assert_eq!(engine.hashfull(), 0);
+    let (best, depth, _) = engine.pick_and_stats(&mut pos);
+    assert_eq!(depth, 1);
+    assert!(all_moves(&pos).iter().any(|mv| mv == best));
+}
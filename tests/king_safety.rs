@@ -0,0 +1,46 @@
+use chess::engines::engine_manager::Engine;
+use chess::position::Position;
+
+/// King-safety part of the eval: eval 4 minus the eval it builds on.
+fn king_safety_term(fen: &str) -> i16 {
+    let pos = Position::from_fen(fen).unwrap();
+    Engine::new(29, 4, 0).evaluate(&pos) - Engine::new(29, 3, 0).evaluate(&pos)
+}
+
+#[test]
+fn symmetric_positions_cancel_out() {
+    assert_eq!(king_safety_term("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), 0);
+    assert_eq!(king_safety_term("r4rk1/5ppp/8/8/8/8/5PPP/R4RK1 w - - 0 1"), 0);
+}
+
+#[test]
+fn pawn_shield_and_open_files() {
+    let intact = king_safety_term("r4rk1/5ppp/8/8/8/8/5PPP/R4RK1 w - - 0 1");
+    let advanced = king_safety_term("r4rk1/5ppp/8/8/8/6P1/5P1P/R4RK1 w - - 0 1");
+    let pushed_away = king_safety_term("r4rk1/5ppp/8/8/6P1/8/5P1P/R4RK1 w - - 0 1");
+    let semi_open = king_safety_term("r4rk1/5ppp/8/8/8/8/5P1P/R4RK1 w - - 0 1");
+    assert!(advanced < intact);
+    assert!(pushed_away < advanced);
+    // with the g-pawn gone the file is also semi-open
+    assert!(semi_open < pushed_away);
+}
+
+#[test]
+fn attackers_near_the_king_count() {
+    let near = king_safety_term("6k1/5ppp/8/8/6nq/8/5PPP/3Q2K1 w - - 0 1");
+    let far = king_safety_term("6k1/5ppp/n7/q7/8/8/5PPP/3Q2K1 w - - 0 1");
+    assert!(near < far);
+
+    // a lone attacker is not an attack yet
+    let queen_only = king_safety_term("6k1/5ppp/8/8/7q/8/5PPP/3Q2K1 w - - 0 1");
+    let queen_far = king_safety_term("6k1/5ppp/8/q7/8/8/5PPP/3Q2K1 w - - 0 1");
+    assert_eq!(queen_only, queen_far);
+}
+
+#[test]
+fn fades_out_in_the_endgame() {
+    // the same exposed king matters less with only rooks left than with queens on
+    let queens = king_safety_term("3q2k1/5ppp/8/8/8/8/5P1P/3Q2K1 w - - 0 1");
+    let rooks = king_safety_term("3r2k1/5ppp/8/8/8/8/5P1P/3R2K1 w - - 0 1");
+    assert!(queens < rooks && rooks < 0);
+}
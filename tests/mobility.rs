@@ -0,0 +1,61 @@
+use chess::engines::engine_manager::Engine;
+use chess::position::Position;
+
+/// Mobility and piece-activity part of the eval: eval 5 minus the eval it builds on.
+fn activity_term(fen: &str) -> i16 {
+    let pos = Position::from_fen(fen).unwrap();
+    Engine::new(29, 5, 0).evaluate(&pos) - Engine::new(29, 4, 0).evaluate(&pos)
+}
+
+/// The same position with the colours swapped (board flipped, side to move swapped).
+fn mirrored(fen: &str) -> String {
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    let board: Vec<String> = fields[0].split('/').rev().map(|rank| {
+        rank.chars().map(|c| if c.is_ascii_uppercase() { c.to_ascii_lowercase() } else { c.to_ascii_uppercase() }).collect()
+    }).collect();
+    let turn = if fields[1] == "w" { "b" } else { "w" };
+    format!("{} {turn} - - 0 1", board.join("/"))
+}
+
+#[test]
+fn colours_are_treated_alike() {
+    assert_eq!(activity_term("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), 0);
+    for fen in [
+        "r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N2N2/PP2BPPP/R2QKB1R w KQ - 0 8",
+        "4k3/p7/8/3N4/2P5/8/8/4K3 w - - 0 1",
+        "2r3k1/5ppp/8/8/8/8/B4PPP/6K1 b - - 0 1",
+    ] {
+        assert_eq!(activity_term(fen), -activity_term(&mirrored(fen)), "{fen}");
+    }
+}
+
+#[test]
+fn rooks_like_open_files_and_the_seventh() {
+    let open = activity_term("3k4/pppp1ppp/8/8/8/8/PPPP1PPP/4RK2 w - - 0 1");
+    let closed = activity_term("3k4/pppppppp/8/8/8/8/PPPPPPPP/4RK2 w - - 0 1");
+    assert!(open > closed);
+
+    let seventh = activity_term("4k3/R7/8/8/8/8/7P/6K1 w - - 0 1");
+    let sixth = activity_term("4k3/8/R7/8/8/8/7P/6K1 w - - 0 1");
+    assert!(seventh > sixth);
+}
+
+#[test]
+fn knight_outposts() {
+    // no black pawn can ever attack d5 in the first position; ...c6 would in the second
+    let outpost = activity_term("4k3/p7/8/3N4/2P5/8/8/4K3 w - - 0 1");
+    let chased = activity_term("4k3/2p5/8/3N4/2P5/8/8/4K3 w - - 0 1");
+    assert!(outpost > chased);
+}
+
+#[test]
+fn trapped_pieces() {
+    let trapped = activity_term("4k3/B7/1p6/8/8/8/5P2/6K1 w - - 0 1");
+    let free = activity_term("4k3/8/7p/8/8/8/B4P2/6K1 w - - 0 1");
+    assert!(trapped < free);
+
+    // the king on f1 shuts the rook in; on e1 it simply has not moved yet
+    let boxed_in = activity_term("4k3/pppppppp/8/8/8/8/PPPPPPPP/5KNR w - - 0 1");
+    let uncastled = activity_term("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K1NR w - - 0 1");
+    assert!(boxed_in < uncastled);
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use chess::attacks::movegen::all_moves;
+use chess::engines::engine_manager::Engine;
+use chess::engines::nnue::{Network, FILE_SIZE};
+use chess::position::Position;
+
+/// A network with small random weights: meaningless as chess, but every input matters.
+fn random_network(seed: u64) -> Arc<Network> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let bytes: Vec<u8> = (0..FILE_SIZE / 2)
+        .flat_map(|_| rng.random_range(-64i16..=64).to_le_bytes())
+        .collect();
+    Arc::new(Network::from_bytes(&bytes).unwrap())
+}
+
+/// The network's eval of `pos` with the accumulators built from scratch.
+fn fresh_eval(pos: &Position, network: &Arc<Network>) -> Option<i16> {
+    let mut pos = pos.clone();
+    pos.set_network(Some(network.clone()));
+    pos.evaluate_nnue()
+}
+
+#[test]
+fn short_files_are_rejected() {
+    assert!(Network::from_bytes(&vec![0; FILE_SIZE - 1]).is_err());
+    assert!(Network::from_bytes(&vec![0; FILE_SIZE + 16]).is_ok());
+}
+
+#[test]
+fn incremental_updates_match_a_refresh() {
+    let network = random_network(1);
+    let mut rng = StdRng::seed_from_u64(2);
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        // castling both ways, captures and en passant
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        // promotions, with and without capture
+        "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+    ] {
+        for _ in 0..20 {
+            let mut pos = Position::from_fen(fen).unwrap();
+            pos.set_network(Some(network.clone()));
+            let start = pos.evaluate_nnue();
+            let mut played = 0;
+            for _ in 0..12 {
+                let moves: Vec<_> = all_moves(&pos).iter().collect();
+                if moves.is_empty() {
+                    break;
+                }
+                pos.do_move(moves[rng.random_range(0..moves.len())]);
+                played += 1;
+                assert_eq!(pos.evaluate_nnue(), fresh_eval(&pos, &network), "{}", pos.to_fen());
+            }
+            for _ in 0..played {
+                pos.undo_move();
+            }
+            assert_eq!(pos.evaluate_nnue(), start, "{fen}");
+        }
+    }
+}
+
+#[test]
+fn colours_are_treated_alike() {
+    let network = random_network(3);
+    let pos = Position::from_fen("r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N2N2/PP2BPPP/R2QKB1R w KQ - 0 8").unwrap();
+    let mirrored = Position::from_fen("r2qkb1r/pp2bppp/2n2n2/2pp4/3P4/2N1PN2/PP2BPPP/R1BQ1RK1 b kq - 0 8").unwrap();
+    assert_eq!(fresh_eval(&pos, &network).unwrap(), -fresh_eval(&mirrored, &network).unwrap());
+}
+
+#[test]
+fn engine_uses_the_network_when_it_has_one() {
+    let pos = Position::from_fen("r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N2N2/PP2BPPP/R2QKB1R w KQ - 0 8").unwrap();
+    let network = random_network(4);
+
+    let mut engine = Engine::new(29, 6, 0);
+    assert_eq!(engine.evaluate(&pos), Engine::new(29, 5, 0).evaluate(&pos));
+    engine.set_network(Some(network.clone()));
+    assert_eq!(Some(engine.evaluate(&pos)), fresh_eval(&pos, &network));
+
+    // the classical evals ignore it
+    let mut classical = Engine::new(29, 5, 0);
+    classical.set_network(Some(network));
+    assert_eq!(classical.evaluate(&pos), Engine::new(29, 5, 0).evaluate(&pos));
+}
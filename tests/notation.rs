@@ -0,0 +1,96 @@
+use chess::attacks::movegen::all_moves;
+use chess::notation::MoveParseError;
+use chess::position::Position;
+
+mod perft_positions;
+use perft_positions::PERFT_POSITIONS;
+
+fn san_of(fen: &str, uci: &str) -> String {
+    let pos = Position::load_position_from_fen(fen);
+    let mv = all_moves(&pos).iter().find(|mv| mv.to_string() == uci).expect(uci);
+    pos.move_to_san(mv)
+}
+
+#[test]
+fn san_formatting() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    assert_eq!(san_of(start, "e2e4"), "e4");
+    assert_eq!(san_of(start, "g1f3"), "Nf3");
+
+    // captures, en passant and castling
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    assert_eq!(san_of(fen, "e5f7"), "Nxf7");
+    assert_eq!(san_of(fen, "d5e6"), "dxe6");
+    assert_eq!(san_of(fen, "e1g1"), "O-O");
+    assert_eq!(san_of(fen, "e1c1"), "O-O-O");
+    assert_eq!(san_of("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2", "e5d6"), "exd6");
+
+    // disambiguation by file, rank and square
+    assert_eq!(san_of("4k3/8/8/8/8/8/4K3/R6R w - - 0 1", "a1d1"), "Rad1");
+    assert_eq!(san_of("R7/7k/8/8/8/8/8/R3K3 w - - 0 1", "a1a5"), "R1a5");
+    assert_eq!(san_of("8/7k/8/8/Q2Q4/8/8/Q3K3 w - - 0 1", "a4d1"), "Qa4d1");
+
+    // promotions, check and mate
+    assert_eq!(san_of("3qk3/2P5/8/8/8/8/8/4K3 w - - 0 1", "c7d8q"), "cxd8=Q+");
+    assert_eq!(san_of("3qk3/2P5/8/8/8/8/8/4K3 w - - 0 1", "c7c8n"), "c8=N");
+    assert_eq!(san_of("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8"), "Ra8#");
+}
+
+#[test]
+fn san_parsing() {
+    let pos = Position::load_position_from_fen("4k3/8/8/8/8/8/4K3/R6R w - - 0 1");
+    assert_eq!(pos.parse_san("Rad1").unwrap().to_string(), "a1d1");
+    assert_eq!(pos.parse_san("Rhxd1!?").unwrap().to_string(), "h1d1");
+    assert_eq!(pos.parse_san("Rd1"), Err(MoveParseError::Ambiguous("Rd1".to_string())));
+
+    let pos = Position::load_position_from_fen("4k3/8/8/8/8/8/8/R3K2R w K - 0 1");
+    assert_eq!(pos.parse_san("O-O").unwrap().to_string(), "e1g1");
+    assert_eq!(pos.parse_san("0-0+").unwrap().to_string(), "e1g1");
+    assert_eq!(pos.parse_san("O-O-O"), Err(MoveParseError::Illegal("O-O-O".to_string())));
+    assert_eq!(pos.parse_san("Rz9"), Err(MoveParseError::Syntax("Rz9".to_string())));
+    assert_eq!(pos.parse_san("Nf3"), Err(MoveParseError::Illegal("Nf3".to_string())));
+
+    let pos = Position::load_position_from_fen("3qk3/2P5/8/8/8/8/8/4K3 w - - 0 1");
+    assert_eq!(pos.parse_san("cxd8=Q+").unwrap().to_string(), "c7d8q");
+    assert_eq!(pos.parse_san("c8=R").unwrap().to_string(), "c7c8r");
+    assert!(matches!(pos.parse_san("c8"), Err(MoveParseError::Illegal(_))));
+}
+
+#[test]
+fn san_round_trip() {
+    for entry in PERFT_POSITIONS {
+        let pos = Position::load_position_from_fen(entry.fen);
+        for mv in all_moves(&pos).iter() {
+            let san = pos.move_to_san(mv);
+            assert_eq!(pos.parse_san(&san), Ok(mv), "{} in {}", san, entry.fen);
+        }
+    }
+}
+
+#[test]
+fn uci_parsing() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    let pos = Position::load_position_from_fen(fen);
+    assert!(pos.parse_uci_move("a2a4").unwrap().is_double_push());
+    assert!(pos.parse_uci_move("e1g1").unwrap().is_king_castle());
+    assert!(pos.parse_uci_move("e1c1").unwrap().is_queen_castle());
+    assert!(pos.parse_uci_move("e5f7").unwrap().is_capture());
+    assert_eq!(pos.parse_uci_move("e1e3"), Err(MoveParseError::Illegal("e1e3".to_string())));
+    assert_eq!(pos.parse_uci_move("e1"), Err(MoveParseError::Syntax("e1".to_string())));
+    assert_eq!(pos.parse_uci_move("a2a4k"), Err(MoveParseError::Syntax("a2a4k".to_string())));
+
+    let pos = Position::load_position_from_fen("3qk3/2P5/8/8/8/8/8/4K3 w - - 0 1");
+    let mv = pos.parse_uci_move("c7d8n").unwrap();
+    assert!(mv.is_promotion() && mv.is_capture());
+    assert!(pos.parse_uci_move("c7c8").is_err());
+
+    let pos = Position::load_position_from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2");
+    assert!(pos.parse_uci_move("e5d6").unwrap().is_en_passant());
+
+    for entry in PERFT_POSITIONS {
+        let pos = Position::load_position_from_fen(entry.fen);
+        for mv in all_moves(&pos).iter() {
+            assert_eq!(pos.parse_uci_move(&mv.to_string()), Ok(mv));
+        }
+    }
+}
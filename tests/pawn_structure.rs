@@ -0,0 +1,74 @@
+use chess::attacks::movegen::all_moves;
+use chess::engines::engine_manager::Engine;
+use chess::position::Position;
+
+/// Pawn-structure part of the eval: eval 3 minus the eval it builds on.
+fn pawn_term(fen: &str) -> i16 {
+    let pos = Position::from_fen(fen).unwrap();
+    Engine::new(29, 3, 0).evaluate(&pos) - Engine::new(29, 2, 0).evaluate(&pos)
+}
+
+fn check_pawn_key(position: &mut Position, depth: u8) {
+    let fen = position.to_fen();
+    let fields: Vec<&str> = fen.split_whitespace().take(4).collect();
+    let rebuilt = Position::load_position_from_fen(&format!("{} 0 1", fields.join(" ")));
+    assert_eq!(position.pawn_key(), rebuilt.pawn_key(), "{fen}");
+
+    if depth == 0 {
+        return;
+    }
+    let key = position.pawn_key();
+    for mv in all_moves(position).iter() {
+        position.do_move(mv);
+        check_pawn_key(position, depth - 1);
+        position.undo_move();
+        assert_eq!(position.pawn_key(), key);
+    }
+}
+
+#[test]
+fn pawn_key_is_incremental() {
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",  // promotions with and without capture
+        "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",         // en passant
+    ] {
+        check_pawn_key(&mut Position::from_fen(fen).unwrap(), 3);
+    }
+
+    // the pawn key ignores everything but the pawns
+    let a = Position::from_fen("4k3/pp6/8/8/8/8/PP6/4K3 w - - 0 1").unwrap();
+    let b = Position::from_fen("r3k3/pp6/8/8/8/8/PP6/1N2K3 b - - 0 1").unwrap();
+    assert_eq!(a.pawn_key(), b.pawn_key());
+    assert_ne!(a.pawn_key(), Position::start().pawn_key());
+}
+
+#[test]
+fn symmetric_structures_cancel_out() {
+    assert_eq!(pawn_term("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), 0);
+    assert_eq!(pawn_term("4k3/p1p2pp1/8/8/8/8/P1P2PP1/4K3 w - - 0 1"), 0);
+}
+
+#[test]
+fn weak_pawns_are_penalised() {
+    // doubled and isolated a-pawns and an isolated c-pawn against a connected chain
+    assert!(pawn_term("4k3/8/ppp5/8/8/P7/P1P5/4K3 w - - 0 1") < 0);
+    // the d-pawn is backward once its neighbours have advanced and e5 covers its stop square
+    assert!(pawn_term("4k3/8/8/4p3/2P1P3/3P4/8/4K3 w - - 0 1") < pawn_term("4k3/8/4p3/8/2P1P3/3P4/8/4K3 w - - 0 1"));
+}
+
+#[test]
+fn passed_pawns_grow_with_rank_and_need_the_king() {
+    // a knight each keeps these out of the bitbases
+    let on_fourth = pawn_term("n3k3/8/8/8/4P3/8/8/N3K3 w - - 0 1");
+    let on_sixth  = pawn_term("n3k3/8/4P3/8/8/8/8/N3K3 w - - 0 1");
+    assert!(on_fourth > 0);
+    assert!(on_sixth > on_fourth);
+
+    // blocked by a piece, it loses the free-path bonus
+    assert!(pawn_term("6k1/4n3/4P3/8/8/8/8/4K3 w - - 0 1") < pawn_term("6k1/n7/4P3/8/8/8/8/4K3 w - - 0 1"));
+    // the enemy king far from the pawn's path is worth more than one standing in front of it
+    assert!(pawn_term("7k/8/8/1P6/8/8/8/K6n w - - 0 1") > pawn_term("1k6/8/8/1P6/8/8/8/K6n w - - 0 1"));
+}
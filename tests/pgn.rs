@@ -0,0 +1,92 @@
+use chess::color::Color;
+use chess::notation::MoveParseError;
+use chess::pgn::{parse_pgn, Game, MoveEval, PgnError};
+use chess::position::{Position, Status};
+
+const MULTI_GAME: &str = r#"
+[Event "Casual"]
+[Site "?"]
+[Date "2024.01.02"]
+[Round "1"]
+[White "Alice"]
+[Black "Bob"]
+[Result "1-0"]
+
+1. e4 {best by test} e5 $1 2. Nf3 (2. f4 exf4 (2... d5 3. exd5) 3. Nf3) 2... Nc6
+3. Bb5 a6!? ; the Morphy defence
+4. Ba4 Nf6 5. O-O Be7 1-0
+
+[Event "Second"]
+[White "Carol"]
+[Black "Dave"]
+[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 b - - 0 40"]
+
+40... Kd7 {-0.50/3} 41. e4 {+1.25/10 0.2s} *
+"#;
+
+#[test]
+fn parse_multiple_games() {
+    let games = parse_pgn(MULTI_GAME).unwrap();
+    assert_eq!(games.len(), 2);
+
+    let first = &games[0];
+    assert_eq!(first.tag("White"), Some("Alice"));
+    assert_eq!(first.result, Status::Checkmate(Color::White));
+    let moves: Vec<String> = first.moves.iter().map(|mv| mv.to_string()).collect();
+    assert_eq!(moves, ["e2e4", "e7e5", "g1f3", "b8c6", "f1b5", "a7a6", "b5a4", "g8f6", "e1g1", "f8e7"]);
+    assert!(first.evals.iter().all(Option::is_none));
+
+    let second = &games[1];
+    assert_eq!(second.start.to_fen(), "4k3/8/8/8/8/8/4P3/4K3 b - - 0 40");
+    assert_eq!(second.result, Status::Ongoing);
+    assert_eq!(second.evals, [Some(MoveEval { score: -50, depth: 3 }), Some(MoveEval { score: 125, depth: 10 })]);
+    assert_eq!(second.end_position().to_fen(), "8/3k4/8/8/4P3/8/8/4K3 b - e3 0 41");
+}
+
+#[test]
+fn write_and_read_back() {
+    let start = Position::load_position_from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+    let mut game = Game::new(start.clone());
+    game.set_tag("White", "Engine [search: 29, eval: 2]");
+    game.set_tag("Annotator", "battle");
+
+    let mut pos = start;
+    for (uci, eval) in [("e7e5", Some(MoveEval { score: 12, depth: 9 })), ("g1f3", None), ("b8c6", None)] {
+        let mv = pos.parse_uci_move(uci).unwrap();
+        game.push(mv, eval);
+        pos.do_move(mv);
+    }
+    game.result = Status::Draw;
+
+    let pgn = game.to_pgn();
+    assert!(pgn.starts_with("[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n"));
+    assert!(pgn.contains("[White \"Engine [search: 29, eval: 2]\"]\n[Black \"?\"]\n[Result \"1/2-1/2\"]\n"));
+    assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1\"]\n[Annotator \"battle\"]\n"));
+    assert!(pgn.ends_with("\n1... e5 {+0.12/9} 2. Nf3 Nc6 1/2-1/2\n"));
+
+    let parsed = parse_pgn(&pgn).unwrap();
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].moves, game.moves);
+    assert_eq!(parsed[0].evals, game.evals);
+    assert_eq!(parsed[0].result, Status::Draw);
+    assert_eq!(parsed[0].tag("White"), Some("Engine [search: 29, eval: 2]"));
+}
+
+#[test]
+fn errors() {
+    let err = parse_pgn("1. e4 e5 2. Ke3 *").unwrap_err();
+    assert_eq!(err, PgnError::Move { game: 1, ply: 3, error: MoveParseError::Illegal("Ke3".to_string()) });
+
+    assert!(matches!(parse_pgn("1. e4 (1. d4 *"), Err(PgnError::Syntax { .. })));
+    assert!(matches!(parse_pgn("1. e4 {unterminated"), Err(PgnError::Syntax { .. })));
+    assert!(matches!(parse_pgn("[FEN \"8/8/8 w - -\"]\n*"), Err(PgnError::Fen { game: 1, .. })));
+}
+
+#[test]
+fn percent_escapes_only_in_first_column() {
+    let games = parse_pgn("%engine output e4 e5\n1. e4 e5 *").unwrap();
+    assert_eq!(games[0].moves.len(), 2);
+
+    assert!(parse_pgn("1. e4 %e5\n*").is_err());
+}
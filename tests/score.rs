@@ -0,0 +1,55 @@
+use chess::engines::engine_manager::{Engine, Search, SearchLimits};
+use chess::engines::score::{from_tt, is_mate, mate_in, mated_in, to_tt, Score, MATE};
+use chess::position::Position;
+
+#[test]
+fn mate_encoding() {
+    assert_eq!(Score::from_search(mate_in(1)), Score::Mate(1));
+    assert_eq!(Score::from_search(mate_in(3)), Score::Mate(2));
+    assert_eq!(Score::from_search(mated_in(2)), Score::Mate(-1));
+    assert_eq!(Score::from_search(mated_in(4)), Score::Mate(-2));
+    assert_eq!(Score::from_search(250), Score::Cp(250));
+    assert_eq!(-Score::Mate(3), Score::Mate(-3));
+    assert!(is_mate(mated_in(10)) && !is_mate(1500));
+
+    // a mate found 7 plies from the root, stored at ply 4 and probed again at ply 2
+    let stored = to_tt(mate_in(7), 4);
+    assert_eq!(stored, MATE - 3);
+    assert_eq!(from_tt(stored, 2), mate_in(5));
+    assert_eq!(from_tt(to_tt(mated_in(6), 3), 3), mated_in(6));
+    assert_eq!(from_tt(to_tt(-42, 9), 1), -42);
+}
+
+fn search(fen: &str, depth: u8) -> Score {
+    search_with(29, 2, fen, depth)
+}
+
+fn search_with(search: u8, eval: u8, fen: &str, depth: u8) -> Score {
+    let mut engine = Engine::new(search, eval, 60_000);
+    engine.set_limits(SearchLimits { depth: Some(depth), ..Default::default() });
+    engine.pick_and_stats(&mut Position::load_position_from_fen(fen)).2
+}
+
+#[test]
+fn reports_mate_distance() {
+    assert_eq!(search("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1", 4), Score::Mate(1));
+    assert_eq!(search("k7/8/2K5/8/8/8/8/7R w - - 0 1", 5), Score::Mate(2));
+    assert_eq!(search("7k/8/6K1/8/8/8/8/R7 b - - 0 1", 5), Score::Mate(-1));
+}
+
+#[test]
+fn every_search_reports_mates() {
+    // search 1 plays random moves
+    for search in (2..=u8::MAX).take_while(|&search| Search::try_from(search).is_ok()) {
+        assert_eq!(search_with(search, 2, "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1", 2), Score::Mate(1), "search {search}");
+    }
+}
+
+#[test]
+fn every_search_uses_the_chosen_eval() {
+    // developed pieces against undeveloped ones: only the mobility eval sees much of it
+    let fen = "rn1qkbnr/pb1ppppp/1p6/8/2BPP3/2N2N2/PPP2PPP/R1BQK2R w KQkq - 0 1";
+    for search in (2..=u8::MAX).take_while(|&search| Search::try_from(search).is_ok()) {
+        assert_ne!(search_with(search, 1, fen, 1), search_with(search, 5, fen, 1), "search {search}");
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use chess::attacks::movegen::all_moves;
+use chess::engines::engine_manager::{Search, SearchLimits};
+use chess::engines::score::Score;
+use chess::position::Position;
+
+mod common;
+use common::engine;
+
+#[test]
+fn depth_limit() {
+    let mut pos = Position::start();
+    let (best, depth, _) = engine(common::depth_limit(3)).pick_and_stats(&mut pos);
+    assert_eq!(depth, 3);
+    assert!(all_moves(&pos).iter().any(|mv| mv == best));
+}
+
+#[test]
+fn node_limit_without_time_limit() {
+    const LIMIT: u64 = 5_000;
+    // every search has to count its nodes, or a node limit never ends it
+    for search in (1..=u8::MAX).take_while(|&search| Search::try_from(search).is_ok()) {
+        let mut pos = Position::start();
+        let mut engine = common::engine_with(search, 2, common::node_limit(LIMIT));
+        let nodes = Arc::new(AtomicU64::new(0));
+        let reported = Arc::clone(&nodes);
+        engine.set_info_callback(move |info| reported.store(info.nodes, Ordering::Relaxed));
+
+        let started = Instant::now();
+        let (best, depth, _) = engine.pick_and_stats(&mut pos);
+        assert!(started.elapsed() < Duration::from_secs(60), "search {search} ignored the node limit");
+        assert!(all_moves(&pos).iter().any(|mv| mv == best));
+        let nodes = nodes.load(Ordering::Relaxed);
+        assert!(nodes <= LIMIT, "search {search} searched {nodes} nodes");
+        if search > 1 {
+            assert!(depth >= 1 && nodes > 0, "search {search} did not count its nodes");
+        }
+    }
+}
+
+#[test]
+fn stop_from_another_thread() {
+    let mut pos = Position::load_position_from_fen("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+    let mut engine = engine(SearchLimits { infinite: true, ..Default::default() });
+    let stop = engine.stop_flag();
+
+    let started = Instant::now();
+    let stopper = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(300));
+        stop.store(true, Ordering::Relaxed);
+    });
+    let (best, depth, _) = engine.pick_and_stats(&mut pos);
+    stopper.join().unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert!(depth >= 1);
+    assert!(all_moves(&pos).iter().any(|mv| mv == best));
+}
+
+#[test]
+fn mate_search_stops_when_found() {
+    let mut pos = Position::load_position_from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    let mut engine = engine(SearchLimits { mate: Some(1), infinite: true, ..Default::default() });
+    let (best, _, score) = engine.pick_and_stats(&mut pos);
+    assert_eq!(best.to_string(), "a1a8");
+    assert_eq!(score, Score::Mate(1));
+}
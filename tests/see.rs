@@ -1,4 +1,3 @@
-use chess::mov::Move;
 use chess::position::Position;
 
 #[test]
@@ -13,13 +12,13 @@ fn see() {
         "r1b1k1nr/1ppqpp1p/8/8/p2N2BP/2P3R1/PPQ2PP1/R1B1K3 b Qkq - 1"
     ];
 
-    let moves: [Move; 6] = [
-        Move::new(18450),
-        Move::new(18154),
-        Move::new(18154),
-        Move::new(18129),
-        Move::new(18316),
-        Move::new(18355),
+    let moves: [&str; 6] = [
+        "c3a5",
+        "c6d4",
+        "c6d4",
+        "b3d4",
+        "e2g4",
+        "d7g4",
 
     ];
     const EXPECTED_SEE_VALUE: [bool; 6] = [
@@ -33,8 +32,9 @@ fn see() {
 
 
     for (i, fen) in FENS.iter().enumerate() {
-        let mut pos = Position::load_position_from_fen(fen);
-        assert_eq!(pos.see(moves[i]), EXPECTED_SEE_VALUE[i]);
+        let pos = Position::load_position_from_fen(fen);
+        let mv = pos.parse_uci_move(moves[i]).unwrap();
+        assert_eq!(pos.see(mv), EXPECTED_SEE_VALUE[i]);
     }
 
 }
\ No newline at end of file
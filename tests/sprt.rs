@@ -0,0 +1,72 @@
+use chess::engines::engine_manager::{Engine, SearchLimits};
+use chess::simulator::engine_battle_simulator::sprt_battles;
+use chess::simulator::stats::{elo_from_score, score_from_elo, MatchStats, Sprt, SprtDecision};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 0.05
+}
+
+#[test]
+fn elo_and_los() {
+    assert!(close(score_from_elo(0.0), 0.5));
+    assert!(close(elo_from_score(score_from_elo(100.0)), 100.0));
+
+    let stats = MatchStats::from_games(60, 40, 0);
+    assert!(close(stats.score(), 0.6));
+    let elo = stats.elo();
+    assert!(close(elo.elo, 70.44));
+    // 95%: score 0.6 +- 1.96 * sqrt(0.24 / 100)
+    assert!(close(elo.lower, elo_from_score(0.6 - 0.0960)) && close(elo.upper, elo_from_score(0.6 + 0.0960)));
+    assert!(close(stats.los(), 0.9772));
+
+    assert!(close(MatchStats::from_games(10, 10, 30).los(), 0.5));
+    assert!(close(MatchStats::default().elo().elo, 0.0));
+}
+
+#[test]
+fn pairs_fill_the_pentanomial() {
+    let mut stats = MatchStats::default();
+    for pair in [(2, 0, 0), (1, 1, 0), (0, 0, 2), (1, 0, 1), (0, 1, 1), (0, 2, 0), (1, 0, 1)] {
+        stats.add_pair(pair);
+    }
+    assert_eq!(stats.pentanomial, [1, 1, 2, 2, 1]);
+    assert_eq!((stats.wins, stats.losses, stats.draws, stats.pairs()), (5, 4, 5, 7));
+    assert!(stats.to_string().contains("Pairs: 7 [1, 1, 2, 2, 1]"));
+}
+
+#[test]
+fn sprt_decisions() {
+    let sprt = Sprt { elo0: 0.0, elo1: 10.0, alpha: 0.05, beta: 0.05 };
+    let (lower, upper) = sprt.bounds();
+    assert!(close(lower, -2.944) && close(upper, 2.944));
+
+    let pairs = |counts: [usize; 5]| {
+        let mut stats = MatchStats::default();
+        for (score, &count) in counts.iter().enumerate() {
+            let pair = match score { 0 => (0, 2, 0), 1 => (0, 1, 1), 2 => (0, 0, 2), 3 => (1, 0, 1), _ => (2, 0, 0) };
+            (0..count).for_each(|_| stats.add_pair(pair));
+        }
+        stats
+    };
+    assert_eq!(sprt.decision(&MatchStats::default()), SprtDecision::Continue);
+    assert_eq!(sprt.decision(&pairs([5, 20, 50, 20, 5])), SprtDecision::Continue);
+    assert_eq!(sprt.decision(&pairs([50, 200, 500, 300, 100])), SprtDecision::AcceptH1);
+    assert_eq!(sprt.decision(&pairs([100, 300, 500, 200, 50])), SprtDecision::AcceptH0);
+    // every pair won: no variance, but still a decision
+    assert_eq!(sprt.decision(&pairs([0, 0, 0, 0, 10])), SprtDecision::AcceptH1);
+}
+
+#[test]
+fn stops_early_against_a_much_weaker_engine() {
+    let engine = |search| {
+        let mut engine = Engine::new(search, 5, 0);
+        engine.set_limits(SearchLimits { nodes: Some(200), infinite: true, ..SearchLimits::default() });
+        engine
+    };
+    let sprt = Sprt { elo0: 0.0, elo1: 50.0, alpha: 0.05, beta: 0.05 };
+    // search 1 plays random moves
+    let (stats, decision) = sprt_battles(&engine(29), &engine(1), sprt, 200);
+    assert_eq!(decision, SprtDecision::AcceptH1);
+    assert!(stats.pairs() < 200);
+    assert!(stats.wins > stats.losses);
+}
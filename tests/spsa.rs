@@ -0,0 +1,48 @@
+use std::fs;
+use chess::engines::search_params::{SearchParams, PARAM_SPECS};
+use chess::simulator::spsa::Spsa;
+
+#[test]
+fn params_by_name() {
+    let mut params = SearchParams::default();
+    assert_eq!(params.get("RfpMargin2"), Some(150));
+    assert_eq!(params.get("rfpmargin2"), Some(150));
+    assert!(params.set("FutMargin1", 85));
+    assert_eq!(params.fut_margin_1, 85);
+    assert!(!params.set("NoSuchMargin", 1));
+
+    // clamped to the bounds
+    assert!(params.set("NmpDivisor", 0));
+    assert_eq!(params.nmp_divisor, 1);
+    assert_eq!(SearchParams::from_values(params.values()), params);
+    assert_eq!(params.values().len(), PARAM_SPECS.len());
+}
+
+#[test]
+fn logs_iterations_and_resumes() {
+    let log = std::env::temp_dir().join("chess_spsa_test.csv");
+    let _ = fs::remove_file(&log);
+    let spsa = |iterations| Spsa { iterations, pairs: 1, threads: 1, nodes: Some(200), ..Spsa::new() };
+
+    spsa(1).run(&log).unwrap();
+    // an interrupted run: half a line at the end
+    let mut text = fs::read_to_string(&log).unwrap();
+    text.push_str("2,70.5");
+    fs::write(&log, &text).unwrap();
+
+    let params = spsa(2).run(&log).unwrap();
+    let text = fs::read_to_string(&log).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("iteration,FutMargin1,"));
+    assert!(lines[1].starts_with("1,") && lines[2].starts_with("2,"));
+
+    // the returned parameters are the last logged ones, rounded
+    let last: Vec<f64> = lines[2].split(',').skip(1).take(PARAM_SPECS.len()).map(|v| v.parse().unwrap()).collect();
+    let rounded: Vec<i32> = last.iter().map(|v| v.round() as i32).collect();
+    assert_eq!(params.values().to_vec(), rounded);
+
+    // nothing left to do
+    assert_eq!(spsa(2).run(&log).unwrap(), params);
+    let _ = fs::remove_file(&log);
+}
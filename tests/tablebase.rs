@@ -0,0 +1,96 @@
+#[cfg(feature = "syzygy")]
+use chess::attacks::movegen::all_moves;
+use chess::engines::engine_manager::Engine;
+use chess::engines::score::{MATE_BOUND, TB_WIN};
+use chess::engines::tablebase::{wdl_score, Tablebase, Wdl};
+#[cfg(feature = "syzygy")]
+use chess::position::Position;
+
+mod common;
+
+#[cfg(feature = "syzygy")]
+const KRK: &str = "8/8/8/4k3/8/8/8/R3K3 w - - 0 1";
+
+fn engine() -> Engine {
+    common::engine(common::depth_limit(4))
+}
+
+/// Real tables cannot be shipped with the tests; these run only when `SYZYGY_PATH`
+/// points at a directory holding at least the 3- and 4-piece files.
+#[cfg(feature = "syzygy")]
+fn syzygy_path() -> Option<String> {
+    std::env::var("SYZYGY_PATH").ok().filter(|path| !path.is_empty())
+}
+
+#[test]
+fn tablebase_scores_rank_below_mates() {
+    assert!(wdl_score(Wdl::Win, 0) > wdl_score(Wdl::Win, 5));
+    assert!(wdl_score(Wdl::Win, 0) < MATE_BOUND);
+    assert_eq!(wdl_score(Wdl::Loss, 3), -wdl_score(Wdl::Win, 3));
+    assert_eq!(wdl_score(Wdl::Draw, 7), 0);
+    assert!(wdl_score(Wdl::CursedWin, 1).abs() < 10 && wdl_score(Wdl::BlessedLoss, 1).abs() < 10);
+    assert_eq!(TB_WIN, wdl_score(Wdl::Win, 0));
+}
+
+#[test]
+fn paths_without_tables() {
+    let mut engine = engine();
+    assert_eq!(engine.set_syzygy_path("").unwrap(), 0);
+    assert!(engine.tablebase().is_none());
+    assert!(engine.set_syzygy_path("/no/such/syzygy/dir").is_err());
+}
+
+#[cfg(not(feature = "syzygy"))]
+#[test]
+fn no_tables_without_the_feature() {
+    let err = Tablebase::open(std::env::temp_dir().to_str().unwrap()).err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    assert!(engine().set_syzygy_path(std::env::temp_dir().to_str().unwrap()).is_err());
+}
+
+#[cfg(feature = "syzygy")]
+#[test]
+fn empty_directory() {
+    let empty = std::env::temp_dir().join("chess_tablebase_test_empty");
+    std::fs::create_dir_all(&empty).unwrap();
+    let tb = Tablebase::open(empty.to_str().unwrap()).unwrap();
+    assert_eq!(tb.max_pieces(), 0);
+    let pos = Position::from_fen(KRK).unwrap();
+    assert!(tb.probe_wdl(&pos).is_none());
+    assert!(tb.probe_root(&pos).is_none());
+
+    // with nothing to probe the engine just searches
+    let mut engine = engine();
+    engine.set_syzygy_path(empty.to_str().unwrap()).unwrap();
+    assert!(engine.clone().tablebase().is_some());
+    let best = engine.pick(&pos);
+    assert!(all_moves(&pos).iter().any(|mv| mv == best));
+}
+
+#[cfg(feature = "syzygy")]
+#[test]
+fn probes_real_tables() {
+    let Some(path) = syzygy_path() else { return };
+    let tb = Tablebase::open(&path).unwrap();
+    assert!(tb.max_pieces() >= 4);
+
+    let pos = Position::from_fen(KRK).unwrap();
+    assert_eq!(tb.probe_wdl(&pos), Some(Wdl::Win));
+    let root = tb.probe_root(&pos).unwrap();
+    assert_eq!(root.wdl, Wdl::Win);
+    assert!(root.dtz > 0);
+
+    let drawn = Position::from_fen("8/8/8/4k3/8/8/8/B3K3 w - - 0 1").unwrap();
+    assert_eq!(tb.probe_wdl(&drawn), Some(Wdl::Draw));
+
+    // only exact right after a zeroing move
+    let later = Position::from_fen("8/8/8/4k3/8/8/8/R3K3 w - - 10 20").unwrap();
+    assert!(tb.probe_wdl(&later).is_none());
+
+    // the engine keeps the win from the root instead of searching
+    let mut engine = engine();
+    engine.set_syzygy_path(&path).unwrap();
+    let mut pos = pos;
+    let (best, _, _) = engine.pick_and_stats(&mut pos);
+    assert_eq!(best, root.mv);
+}
@@ -0,0 +1,53 @@
+use std::sync::{Arc, Mutex};
+use chess::attacks::movegen::all_moves;
+use chess::engines::engine_manager::Engine;
+use chess::engines::score::Score;
+use chess::position::Position;
+
+mod common;
+
+fn engine(threads: usize, depth: u8) -> Engine {
+    let mut engine = common::engine(common::depth_limit(depth));
+    engine.set_threads(threads);
+    engine
+}
+
+#[test]
+fn thread_count() {
+    let mut engine = Engine::new(29, 2, 100);
+    assert_eq!(engine.threads(), 1);
+    engine.set_threads(4);
+    assert_eq!(engine.threads(), 4);
+    assert_eq!(engine.clone().threads(), 4);
+    engine.set_threads(0);
+    assert_eq!(engine.threads(), 1);
+}
+
+#[test]
+fn lazy_smp_search() {
+    let mut pos = Position::load_position_from_fen("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+    let mut engine = engine(3, 5);
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&reported);
+    engine.set_info_callback(move |info| sink.lock().unwrap().push(info.nodes));
+
+    let (best, depth, _) = engine.pick_and_stats(&mut pos);
+    assert_eq!(depth, 5);
+    assert!(all_moves(&pos).iter().any(|mv| mv == best));
+
+    // combined counts never shrink between iterations and end at most at the final total
+    let reported = reported.lock().unwrap();
+    assert_eq!(reported.len(), 5);
+    assert!(reported.windows(2).all(|w| w[0] <= w[1]));
+    assert!(*reported.last().unwrap() <= engine.nodes());
+}
+
+#[test]
+fn threads_agree_on_mate() {
+    for threads in [1, 4] {
+        let mut pos = Position::load_position_from_fen("k7/8/2K5/8/8/8/8/7R w - - 0 1");
+        let (_, _, score) = engine(threads, 5).pick_and_stats(&mut pos);
+        assert_eq!(score, Score::Mate(2), "{threads} threads");
+    }
+}
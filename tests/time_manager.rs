@@ -0,0 +1,53 @@
+use std::time::Duration;
+use chess::engines::time_manager::{Clock, TimeManager};
+use chess::mov::Move;
+
+#[test]
+fn budgets_within_the_clock() {
+    let tm = TimeManager::new(Clock::new(60_000, 1000), 20);
+    assert!(tm.soft_limit() <= tm.hard_limit());
+    assert!(tm.soft_limit() >= Duration::from_millis(1000));
+    assert!(tm.hard_limit() < Duration::from_millis(60_000));
+
+    // last move before the time control: plenty to spend, but never the whole clock
+    let last = TimeManager::new(Clock { remaining: 100, increment: 0, moves_to_go: Some(1) }, 10);
+    assert!(last.hard_limit() < Duration::from_millis(90));
+    assert!(last.soft_limit() <= last.hard_limit());
+
+    // overhead larger than the clock still leaves a (tiny) budget
+    let flagging = TimeManager::new(Clock::new(5, 0), 20);
+    assert_eq!(flagging.hard_limit(), Duration::from_millis(1));
+}
+
+#[test]
+fn fixed_time_uses_everything() {
+    let mut tm = TimeManager::fixed(300);
+    assert_eq!(tm.limit(), Duration::from_millis(300));
+    assert!(!tm.iteration_done(Move::encode(12, 28, 1), 30));
+    assert_eq!(tm.limit(), Duration::from_millis(300));
+}
+
+#[test]
+fn stability_and_score_drops() {
+    let e4 = Move::encode(12, 28, 1);
+    let d4 = Move::encode(11, 27, 1);
+    let mut tm = TimeManager::new(Clock::new(300_000, 0), 0);
+
+    tm.iteration_done(e4, 30);
+    let unsure = tm.limit();
+    for _ in 0..4 {
+        tm.iteration_done(e4, 30);
+    }
+    let stable = tm.limit();
+    assert!(stable < tm.soft_limit());
+    assert!(stable < unsure);
+
+    // same move, but the score fell: think longer
+    tm.iteration_done(e4, -60);
+    assert!(tm.limit() > stable);
+
+    // a new best move resets the stability bonus
+    tm.iteration_done(d4, -60);
+    assert!(tm.limit() > tm.soft_limit());
+    assert!(tm.limit() <= tm.hard_limit());
+}
@@ -0,0 +1,62 @@
+use chess::simulator::engine_battle_simulator::Openings;
+use chess::simulator::stats::MatchStats;
+use chess::simulator::tournament::{EngineSpec, Format, Standings, Tournament};
+
+fn spec(search: u8) -> EngineSpec {
+    EngineSpec { search, eval: 5, time_ms: 0 }
+}
+
+#[test]
+fn engine_specs() {
+    assert_eq!("29:5:10".parse::<EngineSpec>(), Ok(EngineSpec { search: 29, eval: 5, time_ms: 10 }));
+    assert!("29:5".parse::<EngineSpec>().is_err());
+    assert!("99:5:10".parse::<EngineSpec>().is_err());
+    assert!("29:0:10".parse::<EngineSpec>().is_err());
+    let all = EngineSpec::all_searches(5, 10);
+    assert_eq!(all.len(), 30);
+    assert_eq!((all[0].search, all[29].search), (1, 30));
+}
+
+#[test]
+fn ratings_follow_the_results() {
+    // 0 beats 1 beats 2, and 0 beats 2 by the most
+    let mut results = vec![vec![MatchStats::default(); 3]; 3];
+    let mut set = |i: usize, j: usize, (w, l, d)| {
+        results[i][j] = MatchStats::from_games(w, l, d);
+        results[j][i] = MatchStats::from_games(l, w, d);
+    };
+    set(0, 1, (6, 2, 2));
+    set(1, 2, (6, 2, 2));
+    set(0, 2, (8, 0, 2));
+    let standings = Standings { engines: vec![spec(27), spec(28), spec(29)], results };
+
+    let ratings = standings.ratings();
+    assert_eq!(ratings.iter().map(|r| r.engine).collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert!(ratings.iter().map(|r| r.elo).sum::<f64>().abs() < 1e-6);
+    assert!(ratings[0].elo > 100.0 && ratings[0].error > 0.0);
+    assert_eq!(ratings[0].games, 20);
+    assert!((ratings[0].score - 0.8).abs() < 1e-9);
+
+    let crosstable = standings.crosstable();
+    assert!(crosstable.lines().nth(1).unwrap().contains("s27 e5 0ms"));
+    assert!(crosstable.contains("7/10") && crosstable.contains("9/10"));
+}
+
+#[test]
+fn every_pairing_plays_its_games() {
+    // search 1 plays random moves
+    let mut tournament = Tournament::new(vec![spec(29), spec(1), spec(1)]);
+    tournament.games = 3;
+    tournament.nodes = Some(200);
+    tournament.openings = Openings::Sequential(100);
+    assert_eq!(tournament.pairings(), vec![(0, 1), (0, 2), (1, 2)]);
+
+    tournament.format = Format::Gauntlet;
+    assert_eq!(tournament.pairings(), vec![(0, 1), (0, 2)]);
+    let standings = tournament.run();
+    // rounded up to whole pairs
+    assert_eq!(standings.results[0][1].games(), 4);
+    assert_eq!(standings.results[1][2].games(), 0);
+    assert_eq!(standings.results[1][0].wins, standings.results[0][1].losses);
+    assert_eq!(standings.ratings()[0].engine, 0);
+}
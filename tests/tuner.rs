@@ -0,0 +1,64 @@
+use std::fs;
+use chess::engines::engine_manager::Engine;
+use chess::position::Position;
+use chess::tuner::{emit_rust, Tuner};
+
+const FENS: [(&str, f64); 6] = [
+    ("r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N2N2/PP2BPPP/R2QKB1R w KQ - 0 8", 0.5),
+    ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 0.5),
+    ("4k3/pp3ppp/8/8/8/8/PP1P1PPP/4K3 w - - 0 1", 1.0),
+    ("r3k2r/1pp2ppp/8/8/8/8/PPP2PPP/4K2R b k - 0 1", 0.0),
+    ("2r3k1/5ppp/8/8/8/8/B4PPP/6K1 b - - 0 1", 0.5),
+    ("4k3/8/3n4/8/8/8/PPP5/4K3 w - - 0 1", 1.0),
+];
+
+#[test]
+fn emitted_source_matches_the_checked_in_params() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/eval_params.rs");
+    assert_eq!(emit_rust(&Tuner::new().params()), fs::read_to_string(path).unwrap());
+}
+
+#[test]
+fn linear_eval_matches_the_engine() {
+    let tuner = Tuner::new();
+    // the hand-written eval the engine plays
+    let engine = Engine::new(29, 5, 0);
+    for (fen, _) in FENS {
+        let pos = Position::from_fen(fen).unwrap();
+        // the engine rounds towards zero once per group of terms, the tuner not at all
+        assert!((tuner.evaluate(&pos) - engine.evaluate(&pos) as f64).abs() < 8.0, "{fen}");
+    }
+}
+
+#[test]
+fn loads_every_format() {
+    let path = std::env::temp_dir().join("chess_tuner_test.txt");
+    fs::write(&path, format!(
+        "{} | 25 | 0.5\n\n{} [1.0]\n{} c9 \"0-1\";\n{} 1/2-1/2\n",
+        FENS[0].0, FENS[2].0, FENS[3].0, "4k3/8/3n4/8/8/8/PPP5/4K3 w - -",
+    )).unwrap();
+    let mut tuner = Tuner::new();
+    assert_eq!(tuner.load(&path).unwrap(), 4);
+
+    fs::write(&path, "not a position\n").unwrap();
+    assert!(tuner.load(&path).is_err());
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn tuning_lowers_the_error() {
+    let mut tuner = Tuner::new();
+    for (fen, result) in FENS {
+        assert!(tuner.add(&Position::from_fen(fen).unwrap(), result));
+    }
+    let k = tuner.fit_k();
+    assert!(k > 0.0 && k < 4.0);
+    assert!(tuner.error(k) <= tuner.error(k * 2.0) && tuner.error(k) <= tuner.error(k / 2.0));
+
+    let before = tuner.error(k);
+    tuner.epochs = 100;
+    let mut last = before;
+    tuner.tune(k, |_, error| last = error);
+    assert!(last < before);
+    assert_ne!(tuner.params(), Tuner::new().params());
+}
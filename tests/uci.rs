@@ -0,0 +1,65 @@
+use chess::color::Color;
+use chess::engines::engine_manager::{Engine, SearchLimits};
+use chess::engines::score::Score;
+use chess::engines::time_manager::Clock;
+use chess::position::Position;
+use chess::uci::{format_bestmove, format_score, parse_position, GoParams};
+
+#[test]
+fn go_params() {
+    let params = GoParams::parse("wtime 60000 btime 30000 winc 1000 binc 500 movestogo 20");
+    assert_eq!(params.wtime, Some(60000));
+    assert_eq!(params.btime, Some(30000));
+    assert_eq!(params.movestogo, Some(20));
+    assert_eq!(params.clock(Color::White), Some(Clock { remaining: 60000, increment: 1000, moves_to_go: Some(20) }));
+    assert_eq!(params.clock(Color::Black), Some(Clock { remaining: 30000, increment: 500, moves_to_go: Some(20) }));
+
+    let params = GoParams::parse("depth 6 nodes 5000");
+    assert_eq!(params.depth, Some(6));
+    assert_eq!(params.nodes, Some(5000));
+
+    let params = GoParams::parse("wtime 1000 btime 1000 movetime 750");
+    assert_eq!(params.clock(Color::Black), None);
+    assert_eq!(params.limits(Color::Black).movetime, Some(750));
+    assert!(!params.limits(Color::Black).infinite);
+    assert!(GoParams::parse("infinite").limits(Color::White).infinite);
+
+    // no clock and no movetime: only the other limits (or `stop`) end the search
+    let params = GoParams::parse("depth 5 mate 3");
+    assert_eq!(params.clock(Color::White), None);
+    assert_eq!(params.limits(Color::White), SearchLimits { depth: Some(5), mate: Some(3), infinite: true, ..Default::default() });
+}
+
+#[test]
+fn position_command() {
+    let pos = parse_position("startpos moves e2e4 e7e5 g1f3").unwrap();
+    assert_eq!(pos.to_fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+
+    let pos = parse_position("fen 7k/P7/8/8/8/8/8/K7 w - - 0 1 moves a7a8q").unwrap();
+    assert_eq!(pos.to_fen(), "Q6k/8/8/8/8/8/8/K7 b - - 0 1");
+
+    assert!(parse_position("startpos moves e2e5").is_err());
+    assert!(parse_position("somewhere").is_err());
+}
+
+#[test]
+fn scores() {
+    assert_eq!(format_score(Score::Cp(35)), "score cp 35");
+    assert_eq!(format_score(Score::Cp(-120)), "score cp -120");
+    assert_eq!(format_score(Score::Mate(1)), "score mate 1");
+    assert_eq!(format_score(Score::Mate(-2)), "score mate -2");
+}
+
+#[test]
+fn bestmove_without_legal_moves() {
+    let mut engine = Engine::new(29, 2, 100);
+    let mut mated = Position::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    assert_eq!(format_bestmove(engine.pick_and_stats(&mut mated).0), "bestmove 0000");
+
+    // a search stopped before its first iteration still plays a legal move
+    engine.set_limits(SearchLimits { nodes: Some(1), infinite: true, ..Default::default() });
+    let mut start = Position::start();
+    let (best, _, _) = engine.pick_and_stats(&mut start);
+    assert!(!best.is_null());
+    assert_eq!(format_bestmove(best), format!("bestmove {best}"));
+}
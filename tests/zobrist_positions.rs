@@ -1,6 +1,5 @@
 use indicatif::{ProgressBar, ProgressStyle};
 use chess::attacks::movegen::all_moves;
-use chess::mov::Move;
 use chess::position::Position;
 mod perft_positions;
 use perft_positions::PERFT_POSITIONS;
@@ -8,7 +7,7 @@ use perft_positions::PERFT_POSITIONS;
 #[test]
 // Kasparov vs. Topalov, Wijk aan Zee 1999
 fn zobrist_game_1() {
-    let moves = [5900, 2803, 5835, 2942, 1153, 2998, 1282, 3517, 707, 2738, 1357, 6257, 774, 3321, 3028, 19446, 19403, 3194, 1032, 6452, 12420, 3387, 66, 2608, 140, 16060, 1090, 18148, 18115, 2218, 219, 2675, 1422, 3706, 2065, 3633, 1477, 2283, 1903, 3129, 263, 1763, 2258, 18665, 18652, 2804, 18115, 18146, 3332, 2672, 18141, 18473, 5705, 1568, 1179, 18667, 3124, 3192, 19568, 1699, 19282, 17432, 18989, 18000, 1162, 17561, 40, 722, 576, 203, 343, 763, 3313, 19659, 18053, 18081, 20425, 1267, 3647, 1178, 1592, 259, 1877, 6517, 129, 723, 3096, ];
+    let moves = ["e2e4", "d7d6", "d2d4", "g8f6", "b1c3", "g7g6", "c1e3", "f8g7", "d1d2", "c7c6", "f2f3", "b7b5", "g1e2", "b8d7", "e3h6", "g7h6", "d2h6", "c8b7", "a2a3", "e7e5", "e1c1", "d8e7", "c1b1", "a7a6", "e2c1", "e8c8", "c1b3", "e5d4", "d1d4", "c6c5", "d4d1", "d7b6", "g2g3", "c8b8", "b3a5", "b7a8", "f1h3", "d6d5", "h6f4", "b8a7", "h1e1", "d5d4", "c3d5", "b6d5", "e4d5", "e7d6", "d1d4", "c5d4", "e1e7", "a7b6", "f4d4", "b6a5", "b2b4", "a5a4", "d4c3", "d6d5", "e7a7", "a8b7", "a7b7", "d5c4", "c3f6", "a4a3", "f6a6", "a3b4", "c2c3", "b4c3", "a6a1", "c3d2", "a1b2", "d2d1", "h3f1", "d8d2", "b7d7", "d2d7", "f1c4", "b5c4", "b2h8", "d7d3", "h8a8", "c4c3", "a8a4", "d1e1", "f3f4", "f7f5", "b1c1", "d3d2", "a4a7"];
     let fens = [
         "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
         "rnbqkbnr/ppp1pppp/3p4/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2",
@@ -102,12 +101,14 @@ fn zobrist_game_1() {
     let mut move_position = Position::start();
 
     for i in 0..moves.len() {
-        let mov = Move::new(moves[i]);
+        let mov = move_position.parse_uci_move(moves[i]).unwrap();
         move_position.do_move(mov);
         let fen_position = Position::load_position_from_fen(fens[i]);
         println!("Move number: {}, Move: {} ", i+1, mov);
         fen_position.print_board();
         assert_eq!(move_position.zobrist(), fen_position.zobrist());
+        assert_eq!(move_position.to_fen(), fens[i]);
+        assert_eq!(fen_position.to_fen(), fens[i]);
     }
 
 
@@ -118,7 +119,7 @@ fn zobrist_game_1() {
 #[test]
 // random garbage
 fn zobrist_game_2() {
-    let moves = [5900, 2617, 2332, 6517, 23396, 19326, 1350, 2998, 1749, 2285, 2499, 3517, 19367, 19383, 1669, 12220, 8580, 18166, 1025,3899, 6095, 3452, 2527, 3518, 3047, 2934, 3567, 4021, 65463, 2477, 2878, 1894, 1162, 17563, 1357, 2478, 1422, 17821, 2988, 1878, 17547, 2333, 838, 1958, 781, 1438, 268, 918, 837, 45454, 333, 16710, 708, 1635, 1291, 17733, 724, 1241, 651, 789, 74, 16531, 656, 1218, 17626, ];
+    let moves = ["e2e4", "b8a6", "e4e5", "f7f5", "e5f6", "g8f6", "g1f3", "g7g6", "f3d4", "f6d5", "d1h5", "f8g7", "h5g6", "h7g6", "f1c4", "e8g8", "e1g1", "g7d4", "b1a3", "d8e8", "h2h4", "e8f7", "h4h5", "g8g7", "h5h6", "g7f6", "h6h7", "f7g8", "h7g8q", "f6g5", "g8e6", "g5f4", "c2c3", "d4c3", "f2f3", "g6g5", "g2g3", "f4g3", "e6g6", "g3f4", "d2c3", "f4e5", "g1f2", "g5g4", "f2e2", "g4g3", "e2e1", "g3g2", "f1f2", "g2g1q", "f2f1", "g1f1", "e1d2", "d5b4", "d2e3", "f1f3", "e3d2", "b4d3", "d2c2", "f3e2", "c2b1", "d3c1", "a3c2", "c1d3", "c4d3"];
     let fens = [
         "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0",
         "r1bqkbnr/pppppppp/n7/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1",
@@ -190,12 +191,14 @@ fn zobrist_game_2() {
     let mut move_position = Position::start();
 
     for i in 0..moves.len() {
-        let mov = Move::new(moves[i]);
+        let mov = move_position.parse_uci_move(moves[i]).unwrap();
         move_position.do_move(mov);
         let fen_position = Position::load_position_from_fen(fens[i]);
         println!("Move number: {}, Move: {} ", i+1, mov);
         fen_position.print_board();
         assert_eq!(move_position.zobrist(), fen_position.zobrist());
+        // these FENs stop at the half-move clock
+        assert_eq!(move_position.to_fen(), format!("{} {}", fens[i], 1 + (i + 1) / 2));
     }
 }
 